@@ -1,8 +1,10 @@
 //! This crate is used to discover files in a project for being used in the
 //! git-hotspots crate.
 #![warn(missing_docs)]
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
 use std::ops::Not;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::time::Instant;
 
@@ -11,7 +13,9 @@ use rayon::prelude::*;
 use walkdir::{DirEntry, WalkDir};
 
 /// Contains the supported languages. The Undefined variant is used when the
-/// language is not supported.
+/// language is not supported. All of them are detected whatever languages the
+/// parsers are built with; the files without a parser are dropped by the
+/// registry of the parsers.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Lang {
     /// Variant for the Rust language.
@@ -20,6 +24,26 @@ pub enum Lang {
     Go,
     /// Variant for the Lua language.
     Lua,
+    /// Variant for the Python language.
+    Python,
+    /// Variant for the JavaScript language, including JSX.
+    JavaScript,
+    /// Variant for the TypeScript language.
+    TypeScript,
+    /// Variant for TypeScript with JSX.
+    Tsx,
+    /// Variant for the C language.
+    C,
+    /// Variant for the C++ language.
+    Cpp,
+    /// Variant for the Java language.
+    Java,
+    /// Variant for the Ruby language.
+    Ruby,
+    /// Variant for Bash and POSIX shell scripts.
+    Bash,
+    /// Variant for languages defined at runtime, by their name.
+    Custom(String),
     /// Variant for unsupported languages.
     Undefined,
 }
@@ -32,6 +56,15 @@ impl From<&str> for Lang {
             "go" => Lang::Go,
             "rust" => Lang::Rust,
             "lua" => Lang::Lua,
+            "python" => Lang::Python,
+            "javascript" => Lang::JavaScript,
+            "typescript" => Lang::TypeScript,
+            "tsx" => Lang::Tsx,
+            "c" => Lang::C,
+            "cpp" => Lang::Cpp,
+            "java" => Lang::Java,
+            "ruby" => Lang::Ruby,
+            "shell" => Lang::Bash,
             _ => Lang::Undefined,
         }
     }
@@ -52,6 +85,8 @@ pub struct File {
 pub struct Discovery {
     prefixes: Vec<String>,
     not_contains: Vec<String>,
+    skip_prefixes: Vec<PathBuf>,
+    languages: Vec<(String, Vec<String>)>,
 }
 
 impl Discovery {
@@ -65,13 +100,27 @@ impl Discovery {
     pub fn not_contains(&mut self, p: String) {
         self.not_contains.push(p);
     }
+    /// Conditions the discovery to skip files under the given path, for
+    /// example a submodule.
+    pub fn skip_prefix<P: Into<PathBuf>>(&mut self, p: P) {
+        self.skip_prefixes.push(p.into());
+    }
+    /// Detects files with any of the given extensions as the custom language
+    /// with the given name. Custom languages take precedence over the built-in
+    /// ones.
+    pub fn with_language(&mut self, name: String, extensions: Vec<String>) {
+        self.languages.push((name, extensions));
+    }
 
     /// Discovers files in the given path. It filters out files that match the
-    /// conditions set by the `with_prefix` and `not_contains` methods.
+    /// conditions set by the `with_prefix`, `not_contains` and `skip_prefix`
+    /// methods. The `.git` directory is never entered, as its hooks are
+    /// scripts too.
     pub fn discover<P: AsRef<Path>>(&self, path: P) -> Option<Vec<File>> {
         let start = Instant::now();
         let res: Vec<File> = WalkDir::new(&path)
             .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
             .par_bridge()
             .filter_map(result::Result::ok)
             .filter(|p| {
@@ -93,12 +142,15 @@ impl Discovery {
                         .not()
                 }
             })
+            .filter(|p| {
+                self.skip_prefixes
+                    .iter()
+                    .any(|prefix| p.path().starts_with(prefix))
+                    .not()
+            })
             .filter(is_project_file)
             .filter_map(|p| {
-                let lang = match detect_lang::from_path(p.path()) {
-                    Some(lang) => lang.id().into(),
-                    None => Lang::Undefined,
-                };
+                let lang = self.custom(p.path()).unwrap_or_else(|| detect(p.path()));
                 let p = p.path().to_str();
 
                 p.map(|path| File {
@@ -118,6 +170,91 @@ impl Discovery {
     }
 }
 
+impl Discovery {
+    /// Returns the custom language of the file by its extension.
+    fn custom(&self, path: &Path) -> Option<Lang> {
+        let extension = path.extension()?.to_str()?;
+        self.languages
+            .iter()
+            .find(|(_, extensions)| extensions.iter().any(|e| e == extension))
+            .map(|(name, _)| Lang::Custom(name.clone()))
+    }
+}
+
+/// Lines starting with these are only found in C++ headers.
+const CPP_MARKERS: [&str; 8] = [
+    "class ",
+    "namespace ",
+    "template<",
+    "template <",
+    "public:",
+    "private:",
+    "protected:",
+    "using ",
+];
+
+/// Returns the language of the file. Extensions unknown to the detect_lang
+/// crate are looked up separately, `.h` headers are told apart by their
+/// contents, and other files are recognised by their shebang line.
+fn detect(path: &Path) -> Lang {
+    if let Some(lang) = detect_lang::from_path(path) {
+        return match lang.id().into() {
+            Lang::C if path.extension().map_or(false, |e| e == "h") => header(path),
+            lang => lang,
+        };
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("jsx" | "mjs" | "cjs") => Lang::JavaScript,
+        Some("mts" | "cts") => Lang::TypeScript,
+        Some("tsx") => Lang::Tsx,
+        _ => shebang(path),
+    }
+}
+
+/// Returns the language of the interpreter in the shebang line of the file,
+/// like `#!/usr/bin/env bash`.
+fn shebang(path: &Path) -> Lang {
+    let Ok(file) = fs::File::open(path) else {
+        return Lang::Undefined;
+    };
+    let mut line = String::new();
+    if BufReader::new(file.take(256)).read_line(&mut line).is_err() {
+        return Lang::Undefined;
+    }
+    let Some(command) = line.strip_prefix("#!") else {
+        return Lang::Undefined;
+    };
+    let mut words = command.split_whitespace();
+    let mut interpreter = words.next().unwrap_or_default();
+    if interpreter.ends_with("/env") {
+        interpreter = words.find(|w| !w.starts_with('-')).unwrap_or_default();
+    }
+    let name = interpreter.rsplit('/').next().unwrap_or_default();
+    match name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "sh" | "bash" | "dash" | "ksh" => Lang::Bash,
+        "ruby" => Lang::Ruby,
+        "python" => Lang::Python,
+        "node" => Lang::JavaScript,
+        "lua" => Lang::Lua,
+        _ => Lang::Undefined,
+    }
+}
+
+/// Returns C++ if the header uses any C++ constructs, and C otherwise.
+fn header(path: &Path) -> Lang {
+    let Ok(source) = fs::read_to_string(path) else {
+        return Lang::C;
+    };
+    let cpp = source.lines().map(str::trim_start).any(|line| {
+        line.contains("std::") || CPP_MARKERS.iter().any(|m| line.starts_with(m))
+    });
+    if cpp {
+        Lang::Cpp
+    } else {
+        Lang::C
+    }
+}
+
 /// Checks if the given entry is a non-hidden file and not a directory.
 fn is_project_file(entry: &DirEntry) -> bool {
     if entry.file_type().is_dir() {
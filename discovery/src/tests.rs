@@ -65,13 +65,79 @@ fn discovers_recursively() -> Result<(), DynError> {
 #[test]
 fn discovers_language() -> Result<(), DynError> {
     let td = TempDir::new()?;
-    let files = vec!["file1.go", "file2.rs", "file3.lua"];
+    let files = vec![
+        "file1.go",
+        "file2.rs",
+        "file3.lua",
+        "file4.py",
+        "file5.js",
+        "file6.jsx",
+        "file7.ts",
+        "file8.tsx",
+        "file9.c",
+        "file9.cpp",
+        "file9.java",
+        "file9.rb",
+        "file9.sh",
+    ];
     create_files(&td, files)?;
-    let f1 = (&td, "file1.go", Lang::Go).into();
-    let f2 = (&td, "file2.rs", Lang::Rust).into();
-    let f3 = (&td, "file3.lua", Lang::Lua).into();
+    let want = vec![
+        (&td, "file1.go", Lang::Go).into(),
+        (&td, "file2.rs", Lang::Rust).into(),
+        (&td, "file3.lua", Lang::Lua).into(),
+        (&td, "file4.py", Lang::Python).into(),
+        (&td, "file5.js", Lang::JavaScript).into(),
+        (&td, "file6.jsx", Lang::JavaScript).into(),
+        (&td, "file7.ts", Lang::TypeScript).into(),
+        (&td, "file8.tsx", Lang::Tsx).into(),
+        (&td, "file9.c", Lang::C).into(),
+        (&td, "file9.cpp", Lang::Cpp).into(),
+        (&td, "file9.java", Lang::Java).into(),
+        (&td, "file9.rb", Lang::Ruby).into(),
+        (&td, "file9.sh", Lang::Bash).into(),
+    ];
 
-    let want = vec![f1, f2, f3];
+    let d = Discovery::default();
+    let mut res = d.discover(td.path()).unwrap();
+    res.sort_by(|a, b| a.path.cmp(&b.path));
+    assert_that!(res).is_equal_to(&want);
+    Ok(())
+}
+
+#[test]
+fn discovers_header_language() -> Result<(), DynError> {
+    let td = TempDir::new()?;
+    fs::write(td.path().join("a.h"), "int add(int a, int b);\n")?;
+    fs::write(td.path().join("b.h"), "namespace app {\nclass A;\n}\n")?;
+    fs::write(td.path().join("c.h"), "#include <string>\nstd::string name();\n")?;
+    let want = vec![
+        (&td, "a.h", Lang::C).into(),
+        (&td, "b.h", Lang::Cpp).into(),
+        (&td, "c.h", Lang::Cpp).into(),
+    ];
+
+    let d = Discovery::default();
+    let mut res = d.discover(td.path()).unwrap();
+    res.sort_by(|a, b| a.path.cmp(&b.path));
+    assert_that!(res).is_equal_to(&want);
+    Ok(())
+}
+
+#[test]
+fn discovers_shebang_language() -> Result<(), DynError> {
+    let td = TempDir::new()?;
+    fs::write(td.path().join("a"), "#!/bin/bash\necho a\n")?;
+    fs::write(td.path().join("b"), "#!/usr/bin/env -S ruby -w\nputs 1\n")?;
+    fs::write(td.path().join("c"), "#!/usr/bin/python3.11\n")?;
+    fs::write(td.path().join("d"), "just text\n")?;
+    fs::create_dir_all(td.path().join(".git/hooks"))?;
+    fs::write(td.path().join(".git/hooks/pre-commit"), "#!/bin/sh\n")?;
+    let want = vec![
+        (&td, "a", Lang::Bash).into(),
+        (&td, "b", Lang::Ruby).into(),
+        (&td, "c", Lang::Python).into(),
+        (&td, "d", Lang::Undefined).into(),
+    ];
 
     let d = Discovery::default();
     let mut res = d.discover(td.path()).unwrap();
@@ -79,3 +145,37 @@ fn discovers_language() -> Result<(), DynError> {
     assert_that!(res).is_equal_to(&want);
     Ok(())
 }
+
+#[test]
+fn discovers_custom_language() -> Result<(), DynError> {
+    let td = TempDir::new()?;
+    let files = vec!["a.dsl", "b.rs", "c.flow"];
+    create_files(&td, files)?;
+    let want = vec![
+        (&td, "a.dsl", Lang::Custom("dsl".to_owned())).into(),
+        (&td, "b.rs", Lang::Rust).into(),
+        (&td, "c.flow", Lang::Custom("dsl".to_owned())).into(),
+    ];
+
+    let mut d = Discovery::default();
+    d.with_language("dsl".to_owned(), vec!["dsl".to_owned(), "flow".to_owned()]);
+    let mut res = d.discover(td.path()).unwrap();
+    res.sort_by(|a, b| a.path.cmp(&b.path));
+    assert_that!(res).is_equal_to(&want);
+    Ok(())
+}
+
+#[test]
+fn skips_prefix() -> Result<(), DynError> {
+    let td = TempDir::new()?;
+    let b = Path::new("b").join("c.txt");
+    let files = vec!["a.txt", b.to_str().unwrap()];
+    create_files(&td, files)?;
+    let want = vec![(&td, "a.txt", Lang::Undefined).into()];
+
+    let mut d = Discovery::default();
+    d.skip_prefix(td.path().join("b"));
+    let res = d.discover(td.path()).unwrap();
+    assert_that!(res).is_equal_to(&want);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! This module prints a file with the frequency of each function next to its
+//! definition.
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use console::style;
+
+use crate::args::Opt;
+use crate::report::Row;
+
+/// Colours from cool to hot in the 256 colour palette.
+const HEAT: [u8; 6] = [226, 220, 214, 208, 202, 196];
+
+/// Prints the file with a gutter showing the frequency of each function on
+/// its definition line. The file is relative to the root.
+pub fn print(opt: &Opt, report: Vec<Row>, file: &str) -> Result<()> {
+    let path = Path::new(&opt.root).join(file);
+    let source = fs::read_to_string(&path)
+        .map_err(|err| anyhow::format_err!("Can't read {}: {err}", path.display()))?;
+
+    let mut rows: HashMap<usize, Row> = HashMap::new();
+    for row in report {
+        match rows.get(&row.line) {
+            Some(r) if r.freq >= row.freq => {},
+            _ => {
+                rows.insert(row.line, row);
+            },
+        }
+    }
+    let max = rows.values().map(|r| r.freq).max().unwrap_or_default();
+    let width = rows
+        .values()
+        .map(|r| r.frequency().len())
+        .max()
+        .unwrap_or_default();
+
+    for (i, line) in source.lines().enumerate() {
+        match rows.get(&(i + 1)) {
+            Some(r) => {
+                let freq = format!("{:>width$}", r.frequency());
+                println!("{} │ {line}", style(freq).color256(heat(r.freq, max)).bold());
+            },
+            None => println!("{:width$} │ {line}", ""),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the colour of the frequency, scaled to the highest one.
+fn heat(freq: usize, max: usize) -> u8 {
+    match freq {
+        0 => HEAT[0],
+        n => HEAT[(n * HEAT.len() - 1) / max],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heats() {
+        let colours = [0, 1, 5, 10].map(|n| heat(n, 10));
+        assert_eq!(colours, [226, 226, 214, 196]);
+    }
+}
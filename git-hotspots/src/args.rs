@@ -1,3 +1,6 @@
+use std::str::FromStr;
+
+use hotspots_insight::trend::Period;
 use structopt::clap::AppSettings::{ColorAuto, ColoredHelp, DisableVersion};
 use structopt::StructOpt;
 
@@ -35,6 +38,55 @@ pub struct Opt {
     #[structopt(short, long, default_value = ".")]
     pub root: String,
 
+    /// Fail if the history of any function can't be examined.
+    #[structopt(long)]
+    pub strict: bool,
+
+    /// Refuse to run on a shallow clone.
+    #[structopt(long)]
+    pub full_history: bool,
+
+    /// The git binary to run.
+    #[structopt(long, default_value = "git")]
+    pub git: String,
+
+    /// Seconds each git command is allowed to run.
+    #[structopt(long, default_value = "300")]
+    pub git_timeout: u64,
+
+    /// Exclude files inside submodules.
+    #[structopt(long)]
+    pub no_submodules: bool,
+
+    /// Sort the results by frequency, age, last-changed, issues, change-rate or
+    /// churn-rate.
+    #[structopt(long, default_value = "frequency", possible_values = SortBy::VARIANTS)]
+    pub sort: SortBy,
+
+    /// Rank functions, whole files, or functions mixed with whole files the
+    /// parser doesn't support.
+    #[structopt(long, default_value = "function", possible_values = Level::VARIANTS)]
+    pub level: Level,
+
+    /// List the distinct issue keys mentioned by the commits of functions.
+    #[structopt(long)]
+    pub issues: bool,
+
+    /// Pattern of issue keys in commit messages. Implies --issues. Defaults to
+    /// \b[A-Z][A-Z0-9]+-[0-9]+\b.
+    #[structopt(long)]
+    pub issue_pattern: Option<String>,
+
+    /// File with the definitions of additional languages. Defaults to
+    /// ~/.config/git-hotspots/languages.json when it exists.
+    #[structopt(long)]
+    pub languages: Option<String>,
+
+    /// Number of threads parsing files and examining their history. Defaults
+    /// to the number of CPUs.
+    #[structopt(long, short, default_value = "0", hide_default_value = true)]
+    pub jobs: usize,
+
     #[structopt(subcommand)]
     pub sub_commands: Option<Command>,
 }
@@ -43,10 +95,185 @@ pub struct Opt {
 pub enum Command {
     /// Print the application version.
     Version,
+
+    /// Show how often functions changed over time.
+    Trend {
+        /// Length of each bucket: week, month or quarter.
+        #[structopt(long, default_value = "month", possible_values = &["week", "month", "quarter"])]
+        period: Period,
+
+        /// Number of buckets to show, ending with the current period.
+        #[structopt(long, default_value = "12")]
+        buckets: usize,
+
+        /// Print the series as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
+
+    /// List the commits that changed a function.
+    Show {
+        /// File containing the function, relative to the root.
+        file: String,
+
+        /// Name of the function, as shown in the reports.
+        function: String,
+
+        /// Print the diff of the function in each commit.
+        #[structopt(long)]
+        patch: bool,
+    },
+
+    /// Roll the functions up to their directories or modules.
+    Group {
+        /// What to group by: directory, or module for Rust, Lua and Python
+        /// modules and Go packages.
+        #[structopt(long, default_value = "directory", possible_values = GroupBy::VARIANTS)]
+        by: GroupBy,
+
+        /// Print the groups as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
+
+    /// Print a file with the frequency of each function next to its
+    /// definition.
+    Annotate {
+        /// File to annotate, relative to the root.
+        file: String,
+    },
+
+    /// Show how many commits changed each line of a function.
+    Heatmap {
+        /// File containing the function, relative to the root.
+        file: String,
+
+        /// Name of the function, as shown in the reports.
+        function: String,
+
+        /// Print the lines as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
+
+    /// Rank functions by how much their change rate increased in the recent
+    /// window compared to the reference window before it.
+    Compare {
+        /// Length of the recent window in days.
+        #[structopt(long, default_value = "90")]
+        days: u32,
+
+        /// Length of the reference window in days. Default: same as days
+        #[structopt(long)]
+        reference_days: Option<u32>,
+
+        /// Print the comparison as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
+}
+
+/// Keys for sorting the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    /// Most changed functions first.
+    Frequency,
+    /// Oldest functions first.
+    Age,
+    /// Most recently changed functions first.
+    LastChanged,
+    /// Functions touched by the most distinct issues first.
+    Issues,
+    /// Most changes per month since the function first appeared first.
+    ChangeRate,
+    /// Most changed lines per month since the function first appeared first.
+    ChurnRate,
+}
+
+impl SortBy {
+    const VARIANTS: &'static [&'static str] = &[
+        "frequency",
+        "age",
+        "last-changed",
+        "issues",
+        "change-rate",
+        "churn-rate",
+    ];
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "frequency" => Ok(SortBy::Frequency),
+            "age" => Ok(SortBy::Age),
+            "last-changed" => Ok(SortBy::LastChanged),
+            "issues" => Ok(SortBy::Issues),
+            "change-rate" => Ok(SortBy::ChangeRate),
+            "churn-rate" => Ok(SortBy::ChurnRate),
+            _ => Err(format!("unknown sort key: {s}")),
+        }
+    }
+}
+
+/// Granularity of the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Functions of supported files.
+    Function,
+    /// All files, counting every commit that changed them.
+    File,
+    /// Functions of supported files and whole files for the rest.
+    Mixed,
+}
+
+impl Level {
+    const VARIANTS: &'static [&'static str] = &["function", "file", "mixed"];
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "function" => Ok(Level::Function),
+            "file" => Ok(Level::File),
+            "mixed" => Ok(Level::Mixed),
+            _ => Err(format!("unknown level: {s}")),
+        }
+    }
+}
+
+/// Keys for grouping the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Directories of the files.
+    Directory,
+    /// Rust, Lua and Python modules, and Go packages.
+    Module,
+}
+
+impl GroupBy {
+    const VARIANTS: &'static [&'static str] = &["directory", "module"];
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "directory" => Ok(GroupBy::Directory),
+            "module" => Ok(GroupBy::Module),
+            _ => Err(format!("unknown grouping: {s}")),
+        }
+    }
 }
 
 impl Opt {
     pub fn new() -> Opt {
-        Opt::from_args()
+        let mut opt = Opt::from_args();
+        opt.issues |= opt.sort == SortBy::Issues || opt.issue_pattern.is_some();
+        opt
     }
 }
@@ -0,0 +1,82 @@
+//! This module compares how often functions changed in a recent window of
+//! time with a reference window before it.
+use anyhow::Result;
+use hotspots_insight::trend::Window;
+use prettytable::{format, Table};
+use serde::Serialize;
+
+use crate::args::Opt;
+use crate::report::{self, Row};
+
+/// Change is the comparison of the windows for a function.
+#[derive(Serialize)]
+struct Change {
+    file: String,
+    line: usize,
+    function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    submodule: Option<String>,
+    recent: usize,
+    reference: usize,
+    /// Difference of the change rates in commits per 30 days.
+    delta: f64,
+}
+
+/// Prints the functions whose change rate increased the most in the last
+/// `days` compared to the `reference_days` before, as a table or as JSON.
+pub fn print(
+    opt: &Opt,
+    report: Vec<Row>,
+    days: u32,
+    reference_days: u32,
+    json: bool,
+) -> Result<()> {
+    let recent = Window::ending(report::now(), days);
+    let reference = recent.before(reference_days);
+    let mut changes: Vec<Change> = report
+        .into_iter()
+        .map(|r| {
+            let timestamps = r.timestamps();
+            Change {
+                recent: recent.count(&timestamps),
+                reference: reference.count(&timestamps),
+                delta: recent.rate(&timestamps) - reference.rate(&timestamps),
+                file: r.file,
+                line: r.line,
+                function: r.func,
+                submodule: r.submodule,
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| b.delta.total_cmp(&a.delta));
+    let changes = changes.into_iter().skip(opt.skip).take(opt.total);
+
+    if json {
+        let changes: Vec<Change> = changes.collect();
+        println!("{}", serde_json::to_string_pretty(&changes)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        bFg->"FILE",
+        bFg->"LINE",
+        bFg->"FUNCTION",
+        bFg->format!("LAST {days}D"),
+        bFg->format!("PREVIOUS {reference_days}D"),
+        bFg->"CHANGE/30D",
+    ]);
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    for c in changes {
+        table.add_row(row![
+            c.file,
+            c.line,
+            c.function,
+            Fr->c.recent,
+            Fr->c.reference,
+            Fr->format!("{:+.1}", c.delta),
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! This module rolls the functions up to the directories or modules they
+//! belong to.
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use anyhow::Result;
+use prettytable::{format, Table};
+use serde::Serialize;
+
+use crate::args::{GroupBy, Opt};
+use crate::report::Row;
+
+/// Group is a directory or module and the functions in it.
+#[derive(Serialize)]
+struct Group {
+    group: String,
+    functions: usize,
+    /// Sum of the frequencies of the functions.
+    frequency: usize,
+    /// The most changed function of the group.
+    hottest: String,
+    hottest_frequency: usize,
+}
+
+/// Prints the directories or modules whose functions changed the most, as a
+/// table or as JSON.
+pub fn print(opt: &Opt, report: Vec<Row>, by: GroupBy, json: bool) -> Result<()> {
+    let mut groups: HashMap<String, Vec<Row>> = HashMap::new();
+    for row in report {
+        groups.entry(key(&row.file, by)).or_default().push(row);
+    }
+    let mut groups: Vec<Group> = groups
+        .into_iter()
+        .map(|(group, rows)| {
+            let hottest = rows.iter().max_by_key(|r| (r.freq, Reverse(&r.file)));
+            Group {
+                functions: rows.len(),
+                frequency: rows.iter().map(|r| r.freq).sum(),
+                hottest: hottest.map(|r| member(&group, r)).unwrap_or_default(),
+                hottest_frequency: hottest.map(|r| r.freq).unwrap_or_default(),
+                group,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.frequency.cmp(&a.frequency).then(a.group.cmp(&b.group)));
+    let groups = groups.into_iter().skip(opt.skip).take(opt.total);
+
+    if json {
+        let groups: Vec<Group> = groups.collect();
+        println!("{}", serde_json::to_string_pretty(&groups)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        bFg->"GROUP",
+        bFg->"FUNCTIONS",
+        bFg->"FREQUENCY",
+        bFg->"HOTTEST",
+        bFg->"HOTTEST FREQUENCY",
+    ]);
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    for g in groups {
+        table.add_row(row![
+            g.group,
+            Fr->g.functions,
+            Fr->g.frequency,
+            g.hottest,
+            Fr->g.hottest_frequency,
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+/// Returns the directory or module of the file. Go packages are directories,
+/// and Rust, Lua and Python modules are files, except for `mod.rs`, `lib.rs`,
+/// `main.rs`, `init.lua` and `__init__.py` which stand for their directory.
+fn key(file: &str, by: GroupBy) -> String {
+    let path = Path::new(file);
+    let dir = path
+        .parent()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if by == GroupBy::Directory {
+        return dir;
+    }
+    let stem = path.file_stem().and_then(OsStr::to_str);
+    match (path.extension().and_then(OsStr::to_str), stem) {
+        (Some("rs"), Some("mod" | "lib" | "main"))
+        | (Some("lua"), Some("init"))
+        | (Some("py"), Some("__init__")) => dir,
+        (Some("rs" | "lua" | "py"), Some(_)) => {
+            path.with_extension("").to_string_lossy().into_owned()
+        },
+        _ => dir,
+    }
+}
+
+/// Returns the name of the function, prefixed by its file when the group is a
+/// directory. Whole files are named by their path in the group.
+fn member(group: &str, row: &Row) -> String {
+    let file = Path::new(&row.file)
+        .strip_prefix(group)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match (file.is_empty(), row.func.is_empty()) {
+        (_, true) => row.file.clone(),
+        (true, false) => row.func.clone(),
+        (false, false) => format!("{file}:{}", row.func),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(key("./a/b/c.go", GroupBy::Directory), "./a/b");
+        assert_eq!(key("./a/b/c.rs", GroupBy::Directory), "./a/b");
+        assert_eq!(key("./a/b/c.go", GroupBy::Module), "./a/b");
+        assert_eq!(key("./a/b/c.rs", GroupBy::Module), "./a/b/c");
+        assert_eq!(key("./a/b/mod.rs", GroupBy::Module), "./a/b");
+        assert_eq!(key("./src/lib.rs", GroupBy::Module), "./src");
+        assert_eq!(key("./a/b.lua", GroupBy::Module), "./a/b");
+        assert_eq!(key("./a/init.lua", GroupBy::Module), "./a");
+        assert_eq!(key("./a/b.py", GroupBy::Module), "./a/b");
+        assert_eq!(key("./a/__init__.py", GroupBy::Module), "./a");
+        assert_eq!(key("./a/ci.yaml", GroupBy::Module), "./a");
+    }
+
+    #[test]
+    fn members() {
+        let row = |file: &str, func: &str| Row::new(file.into(), 1, func.into(), vec![]);
+        assert_eq!(member("./a", &row("./a/b.go", "Run")), "b.go:Run");
+        assert_eq!(member("./a/b", &row("./a/b.rs", "run")), "run");
+        assert_eq!(member("./a", &row("./a/ci.yaml", "")), "./a/ci.yaml");
+    }
+}
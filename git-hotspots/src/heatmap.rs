@@ -0,0 +1,98 @@
+//! This module prints the source of a function with the number of commits
+//! that changed each of its lines.
+use std::path::Path;
+
+use anyhow::Result;
+use hotspots_insight::heat::Line;
+use hotspots_insight::{Function, Inspector};
+use serde::Serialize;
+
+use crate::args::Opt;
+
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// Heatmap is the heat of the lines of a function.
+#[derive(Serialize)]
+struct Heatmap<'a> {
+    file: &'a str,
+    function: &'a str,
+    lines: Vec<Point<'a>>,
+}
+
+/// Point is a line of the function and how many commits changed it.
+#[derive(Serialize)]
+struct Point<'a> {
+    line: usize,
+    changes: usize,
+    text: &'a str,
+}
+
+/// Prints the lines of the function with a gutter showing how often each
+/// line changed, or the same data as JSON. The file is relative to the root.
+pub fn print(
+    opt: &Opt,
+    insighter: &Inspector,
+    file: &str,
+    function: Function,
+    json: bool,
+) -> Result<()> {
+    let path = Path::new(&opt.root).join(file);
+    let path = path.to_str().unwrap_or(file);
+    let lines = insighter.line_heat(path, function)?;
+    if lines.is_empty() {
+        return Err(anyhow::format_err!(
+            "No history found for {} in {file}",
+            function.name
+        ));
+    }
+
+    if json {
+        let heatmap = Heatmap {
+            file,
+            function: function.name,
+            lines: lines
+                .iter()
+                .map(|l| Point {
+                    line: l.number,
+                    changes: l.changes,
+                    text: &l.text,
+                })
+                .collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&heatmap)?);
+        return Ok(());
+    }
+
+    let max = lines.iter().map(|l| l.changes).max().unwrap_or_default();
+    for l in &lines {
+        println!("{:>6} {:>4} {} {}", l.number, l.changes, shade(l, max), l.text);
+    }
+    Ok(())
+}
+
+/// Returns the shade of the line, scaled to the most changed line.
+fn shade(line: &Line, max: usize) -> char {
+    match line.changes {
+        0 => ' ',
+        n => SHADES[(n * SHADES.len() - 1) / max],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shades() {
+        let line = |changes| Line {
+            number: 1,
+            text: String::new(),
+            changes,
+        };
+        let shades = [0, 1, 2, 3, 4, 8]
+            .into_iter()
+            .map(|n| shade(&line(n), 8))
+            .collect::<String>();
+        assert_eq!(shades, " ░░▒▒█");
+    }
+}
@@ -0,0 +1,85 @@
+//! This module loads the definitions of languages that are not built in.
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use anyhow::Result;
+use hotspots_parser::custom::Definition;
+
+use crate::args::Opt;
+
+/// Path of the definitions inside the user's configuration directory, used
+/// when none is given.
+pub const USER_PATH: &str = "git-hotspots/languages.json";
+
+/// Directory inside the root with the queries overriding the built-in ones.
+pub const QUERIES_PATH: &str = ".hotspots/queries";
+
+/// Returns the definitions in the file given by the options, or in the file
+/// of the user's configuration when it exists. Relative paths in the
+/// definitions are resolved against the directory of the file.
+///
+/// The inspected repository is never searched for definitions, as their
+/// grammars are shared libraries loaded into the process, and a repository
+/// shipping one would run its code.
+pub fn load(opt: &Opt) -> Result<Vec<Definition>> {
+    let path = match (&opt.languages, user_config()) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(path)) if path.exists() => path,
+        (None, _) => return Ok(vec![]),
+    };
+    let source = fs::read_to_string(&path)
+        .map_err(|err| anyhow::format_err!("Can't read {}: {err}", path.display()))?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    parse(&source, dir).map_err(|err| {
+        anyhow::format_err!("Invalid language definitions in {}: {err}", path.display())
+    })
+}
+
+/// Returns the path of the definitions in the user's configuration directory,
+/// which is `$XDG_CONFIG_HOME`, or `~/.config` when it isn't set.
+fn user_config() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join(USER_PATH))
+}
+
+/// Parses the definitions and resolves their paths against the directory.
+fn parse(source: &str, dir: &Path) -> serde_json::Result<Vec<Definition>> {
+    let mut definitions: Vec<Definition> = serde_json::from_str(source)?;
+    for def in &mut definitions {
+        def.grammar = dir.join(&def.grammar);
+        def.query = dir.join(&def.query);
+    }
+    Ok(definitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_definitions() {
+        let source = r#"[
+            {
+                "name": "dsl",
+                "extensions": ["dsl", "flow"],
+                "grammar": "/usr/lib/libtree-sitter-dsl.so",
+                "query": "queries/dsl.scm"
+            }
+        ]"#;
+        let definitions = parse(source, Path::new("/repo/.hotspots")).unwrap();
+        assert_eq!(
+            definitions,
+            vec![Definition {
+                name: "dsl".to_owned(),
+                extensions: vec!["dsl".to_owned(), "flow".to_owned()],
+                grammar: "/usr/lib/libtree-sitter-dsl.so".into(),
+                query: "/repo/.hotspots/queries/dsl.scm".into(),
+                symbol: None,
+            }]
+        );
+        assert!(parse(r#"[{"name": "dsl"}]"#, Path::new(".")).is_err());
+    }
+}
@@ -37,18 +37,95 @@ This will print out 50 found functions in the order of how often they have been
 * `--invert-match`, `-v`: Exclude partially matched path.
 * `--exclude-func`, `-F`: Exclude function by partial match.
 * `--root`, `-r`: Root of the project to inspect. Default: .
+* `--strict`: Fail if the history of any function can't be examined.
+* `--full-history`: Refuse to run on a shallow clone.
+* `--git`: The git binary to run. Default: git
+* `--git-timeout`: Seconds each git command is allowed to run. Default: 300
+* `--no-submodules`: Exclude files inside submodules.
+* `--sort`: Sort the results by `frequency`, `age`, `last-changed`, `issues`,
+  `change-rate` or `churn-rate`. Default: frequency
+* `--level`: Rank `function`s, whole `file`s, or functions `mixed` with whole
+  files the parser doesn't support. Default: function
+* `--issues`: List the distinct issue keys mentioned by the commits of functions.
+* `--issue-pattern`: Pattern of issue keys in commit messages. Implies `--issues`. Default:
+  `\b[A-Z][A-Z0-9]+-[0-9]+\b`
+* `--languages`: File with the definitions of additional languages. Defaults to
+  `~/.config/git-hotspots/languages.json` when it exists.
+* `--jobs`, `-j`: Number of threads parsing files and examining their history.
+  Default: number of CPUs
+
+Functions inside submodules are examined in the submodule's repository, and
+labelled with the submodule in the results.
+
+The `CHANGES/MO` and `CHURN/MO` columns show how many commits and changed lines
+a function had per month since it first appeared in the history.
+
+In a shallow clone, frequencies of functions whose history reaches the shallow
+boundary are marked with a `+`, as older changes are not counted.
+
+## Trend
+
+```bash
+git hotspots trend --period month --buckets 12
+```
+Shows how often each function changed in each of the last 12 months as a
+sparkline. Use `--period week` or `--period quarter` for other bucket sizes,
+and `--json` for a machine-readable output.
+
+## Compare
+
+```bash
+git hotspots compare --days 90 --reference-days 90
+```
+Ranks functions by how much their change rate, in commits per 30 days,
+increased in the last 90 days compared to the 90 days before.
+
+## Group
+
+```bash
+git hotspots group --by module
+```
+Rolls the functions up to their directories, or with `--by module` to Rust, Lua
+and Python modules and Go packages. Each group shows the number of functions, the sum
+of their frequencies and the hottest function. Use `--json` for a
+machine-readable output.
+
+## Show
+
+```bash
+git hotspots show src/config.rs parse_config --patch
+```
+Lists every commit that changed a function with its hash, date, author and
+subject. With `--patch` the diff of the function in each commit is printed too.
+
+## Annotate
+
+```bash
+git hotspots annotate src/config.rs
+```
+Prints a file with the frequency of each function in a gutter next to its
+definition. The hotter the function, the more intense the colour.
+
+## Heatmap
+
+```bash
+git hotspots heatmap src/config.rs parse_config
+```
+Prints the source of a function with the number of commits that added or
+changed each line, and a shade that shows the hottest lines. Changed lines are
+followed back through the history of the function. Use `--json` for editor
+plugins.
 */
 #![warn(missing_docs)]
-use std::time::Instant;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use hotspots_discovery::{Discovery, Lang};
-use hotspots_insight::Inspector;
+use hotspots_discovery::Discovery;
+use hotspots_insight::{issues, Error as InsightError, Function, Git, Inspector};
 use hotspots_parser as parser;
-use hotspots_parser::go::GoParser;
-use hotspots_parser::lua::LuaParser;
-use hotspots_parser::rust::RustParser;
-use hotspots_parser::{Container, Parser};
+use hotspots_parser::custom::Definition;
+use hotspots_parser::registry::Registry;
 use indicatif::ProgressBar;
 use log::{debug, info, warn, LevelFilter};
 use prettytable::{format, Table};
@@ -57,7 +134,21 @@ use rayon::prelude::*;
 #[macro_use]
 extern crate prettytable;
 
+mod annotate;
 mod args;
+mod compare;
+mod group;
+mod heatmap;
+mod languages;
+mod report;
+mod show;
+mod trend;
+
+use args::Level;
+use report::Row;
+
+/// Maximum number of issue keys listed for each function.
+const MAX_TICKETS: usize = 5;
 
 fn main() -> Result<()> {
     let opt = args::Opt::new();
@@ -72,126 +163,431 @@ fn main() -> Result<()> {
         })
         .init();
 
+    if opt.jobs > 0 {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(opt.jobs)
+            .build_global()?;
+    }
+
     if let Some(args::Command::Version) = opt.sub_commands {
         println!(
             "git-release version: {}, git commit: {}",
             env!("APP_VERSION"),
             env!("CURRENT_SHA")
         );
+        let registry = Registry::default();
+        let languages: Vec<&str> = registry.languages().collect();
+        println!("languages: {}", languages.join(", "));
         return Ok(());
     }
 
-    let insighter = Inspector::new(&opt.root)?;
+    let insighter = inspector(&opt)?;
+    match &opt.sub_commands {
+        Some(args::Command::Show {
+            file,
+            function,
+            patch,
+        }) => {
+            let function = locate(&opt, &insighter, function)?;
+            return show::print(&opt, &insighter, file, function, *patch);
+        },
+        Some(args::Command::Heatmap {
+            file,
+            function,
+            json,
+        }) => {
+            let function = locate(&opt, &insighter, function)?;
+            return heatmap::print(&opt, &insighter, file, function, *json);
+        },
+        _ => {},
+    }
+    let (mut report, failures) = examine(&opt, &insighter)?;
+    if opt.issues {
+        let pattern = opt.issue_pattern.as_deref().unwrap_or(issues::DEFAULT_PATTERN);
+        let issues = insighter.issues(pattern)?;
+        report
+            .iter_mut()
+            .for_each(|r| r.issues = issues.keys(&r.commits));
+    }
+
+    match opt.sub_commands {
+        Some(args::Command::Trend {
+            period,
+            buckets,
+            json,
+        }) => trend::print(&opt, report, period, buckets, json)?,
+        Some(args::Command::Compare {
+            days,
+            reference_days,
+            json,
+        }) => compare::print(&opt, report, days, reference_days.unwrap_or(days), json)?,
+        Some(args::Command::Group { by, json }) => group::print(&opt, report, by, json)?,
+        Some(args::Command::Annotate { ref file }) => annotate::print(&opt, report, file)?,
+        _ => print_hotspots(&opt, report),
+    }
+
+    if !failures.is_empty() {
+        eprintln!("Could not analyse {} functions:", failures.len());
+        failures.iter().for_each(|err| eprintln!("  {err}"));
+    }
+    Ok(())
+}
+
+/// Returns an Inspector for the root of the project. It returns an error if
+/// the repository is a shallow clone and the complete history is required.
+fn inspector(opt: &args::Opt) -> Result<Inspector> {
+    let git = Git::new(&opt.root)
+        .with_binary(&opt.git)
+        .with_timeout(Duration::from_secs(opt.git_timeout));
+    let insighter = Inspector::with_git(git)?;
+    if insighter.is_shallow() {
+        if opt.full_history {
+            return Err(anyhow::format_err!(
+                "The repository is a shallow clone, run `git fetch --unshallow` first"
+            ));
+        }
+        eprintln!("Warning: shallow clone, frequencies marked with + are truncated");
+    }
+    if insighter.is_partial() {
+        eprintln!("Warning: the repository is a partial clone, missing objects will be fetched");
+    }
+    if opt.no_submodules {
+        Ok(insighter)
+    } else {
+        Ok(insighter.with_submodules()?)
+    }
+}
 
-    let mut go_parser = GoParser::new(Container::new(100))?;
-    let mut rust_parser = RustParser::new(Container::new(100))?;
-    let mut lua_parser = LuaParser::new(Container::new(100))?;
+/// Returns a Discovery for the files selected by the options. The annotate,
+/// show and heatmap subcommands only look at their file.
+fn discoverer(
+    opt: &args::Opt,
+    insighter: &Inspector,
+    definitions: &[Definition],
+) -> Result<Discovery> {
     let mut discoverer = Discovery::default();
-    if let Some(prefixes) = opt.prefix {
+    for def in definitions {
+        discoverer.with_language(def.name.clone(), def.extensions.clone());
+    }
+    if opt.no_submodules {
+        for path in insighter.submodule_paths()? {
+            discoverer.skip_prefix(path);
+        }
+    }
+    if let Some(
+        args::Command::Annotate { file }
+        | args::Command::Show { file, .. }
+        | args::Command::Heatmap { file, .. },
+    ) = &opt.sub_commands
+    {
+        let path = Path::new(&opt.root).join(file);
+        discoverer.with_prefix(path.to_string_lossy().into_owned());
+        return Ok(discoverer);
+    }
+    if let Some(prefixes) = &opt.prefix {
         for prefix in prefixes {
             discoverer.with_prefix(format!("./{prefix}"));
         }
     }
 
-    if let Some(terms) = opt.invert_match {
+    if let Some(terms) = &opt.invert_match {
         for term in terms {
             discoverer.not_contains(term.clone());
         }
     }
+    Ok(discoverer)
+}
 
-    if let Some(terms) = opt.exclude_func {
+/// Returns the registry of the built-in languages and the ones defined for the
+/// project, with the queries of the project. All the parsers are built, so a
+/// broken grammar or query fails before the files are discovered.
+fn registry(opt: &args::Opt, definitions: &[Definition]) -> Result<Registry> {
+    let mut registry = Registry::default();
+    registry.override_queries(Path::new(&opt.root).join(languages::QUERIES_PATH));
+    for def in definitions {
+        registry.register_custom(def.clone());
+    }
+    registry.build()?;
+    Ok(registry)
+}
+
+/// Returns the function with the name in the file of the subcommand. Its lines
+/// are followed when the file can be parsed, as names in the reports, like
+/// `Config.parse`, are labels git can't find. Otherwise git matches the name.
+fn locate<'a>(opt: &args::Opt, insighter: &Inspector, name: &'a str) -> Result<Function<'a>> {
+    let definitions = languages::load(opt)?;
+    let mut registry = registry(opt, &definitions)?;
+    if let Some(locator) = discoverer(opt, insighter, &definitions)?.discover(&opt.root) {
+        for file in locator {
+            let path = file.path.clone();
+            match registry.add_file(file) {
+                Ok(()) | Err(parser::Error::NotCompatible) => {},
+                Err(err) => warn!("Failed to load file {path}: {err}"),
+            }
+        }
+    }
+    for (_, mut parser) in registry.into_parsers() {
+        let functions = parser.find_functions(&ProgressBar::hidden())?;
+        if let Some(f) = functions.iter().find(|f| f.name == name) {
+            return Ok(Function::at(name, f.line, f.end));
+        }
+    }
+    Ok(Function::from(name))
+}
+
+/// Finds all functions in the project and examines their history. Functions
+/// that can't be examined are returned separately, unless the strict option is
+/// set. Depending on the level, whole files are examined instead of, or in
+/// addition to, functions.
+fn examine(opt: &args::Opt, insighter: &Inspector) -> Result<(Vec<Row>, Vec<InsightError>)> {
+    let definitions = languages::load(opt)?;
+    let mut registry = registry(opt, &definitions)?;
+    if let Some(terms) = &opt.exclude_func {
         for term in terms {
-            go_parser.filter_name(term.clone());
-            rust_parser.filter_name(term.clone());
-            lua_parser.filter_name(term);
+            registry.filter_name(term.clone());
+        }
+    }
+
+    let locator = discoverer(opt, insighter, &definitions)?
+        .discover(&opt.root)
+        .ok_or_else(|| anyhow::format_err!("No files found in the current directory"))?;
+    let mut whole_files: Vec<String> = Vec::new();
+    for file in locator {
+        let path = file.path.clone();
+        if opt.level == Level::File {
+            whole_files.push(path);
+            continue;
+        }
+        match registry.add_file(file) {
+            Ok(()) if opt.log_level > 1 => info!("Added {path}"),
+            Ok(()) => {},
+            Err(parser::Error::NotCompatible) if opt.level == Level::Mixed => {
+                whole_files.push(path);
+            },
+            Err(parser::Error::NotCompatible) => {
+                if opt.log_level > 0 {
+                    warn!("Unsupported file: {path}");
+                }
+            },
+            Err(err) => warn!("Failed to load file {path}: {err}"),
         }
     }
+    let pb = ProgressBar::new(0);
+    let parsers = registry.into_parsers();
 
+    let mut report: Vec<Row> = Vec::new();
+    let mut failures: Vec<InsightError> = Vec::new();
+
+    for (name, mut parser) in parsers {
+        let res = parser.find_functions(&pb);
+        let res = match res {
+            Ok(r) => r,
+            Err(parser::Error::NoFilesAdded) => {
+                debug!("Parser {name} didn't find any files");
+                continue;
+            },
+            Err(parser::Error::ParseFile(msg)) => {
+                warn!("Parser {name} encounter an error: {msg}");
+                continue;
+            },
+            Err(err) => {
+                debug!("Parser {name} encounter an error: {err}");
+                return Err(err)?;
+            },
+        };
+
+        let start = Instant::now();
+        let results = res
+            .into_iter()
+            .par_bridge()
+            .map(|f| {
+                pb.inc(1);
+                insighter
+                    .function_history(&f.file, Function::at(&f.name, f.line, f.end))
+                    .map(|commits| Row {
+                        truncated: insighter.truncated(&f.file, &commits),
+                        submodule: insighter.submodule_of(&f.file).map(str::to_owned),
+                        ..Row::new(f.file, f.line, f.name, commits)
+                    })
+            })
+            .collect::<Vec<_>>();
+        debug!("Function hitory examination took {:?}", start.elapsed());
+
+        for res in results {
+            match res {
+                Ok(row) => report.push(row),
+                Err(err) if opt.strict => return Err(err)?,
+                Err(err) => failures.push(err),
+            }
+        }
+    }
+
+    if !whole_files.is_empty() {
+        let history = insighter.file_history()?;
+        report.extend(whole_files.into_iter().filter_map(|path| {
+            let commits = history.get(&path)?.clone();
+            Some(Row {
+                truncated: insighter.truncated(&path, &commits),
+                submodule: insighter.submodule_of(&path).map(str::to_owned),
+                ..Row::new(path, 0, String::new(), commits)
+            })
+        }));
+    }
+
+    pb.finish_with_message("done");
+    Ok((report, failures))
+}
+
+/// Prints the functions with the most changes.
+fn print_hotspots(opt: &args::Opt, mut report: Vec<Row>) {
+    let show_submodules = report.iter().any(|r| r.submodule.is_some());
     let mut table = Table::new();
-    table.set_titles(row![bFg->"FILE", bFg->"LINE", bFg->"FUNCTION", bFg->"FREQUENCY"]);
+    let mut titles = row![
+        bFg->"FILE",
+        bFg->"LINE",
+        bFg->"FUNCTION",
+        bFg->"FREQUENCY",
+        bFg->"AGE",
+        bFg->"LAST CHANGED",
+        bFg->"CHANGES/MO",
+        bFg->"CHURN/MO",
+    ];
+    if opt.issues {
+        titles.add_cell(cell!(bFg->"ISSUES"));
+        titles.add_cell(cell!(bFg->"TICKETS"));
+    }
+    if show_submodules {
+        titles.add_cell(cell!(bFg->"SUBMODULE"));
+    }
+    table.set_titles(titles);
     table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
 
-    if let Some(locator) = discoverer.discover(&opt.root) {
-        locator.into_iter().for_each(|file| {
-            let path = file.path.clone();
-            let err = match file.lang {
-                Lang::Go => go_parser.add_file(file),
-                Lang::Rust => rust_parser.add_file(file),
-                Lang::Lua => lua_parser.add_file(file),
-                _ => {
-                    if opt.log_level > 0 {
-                        warn!("Unsupported file: {path}");
-                    }
-                    return;
-                },
-            };
-            if let Err(err) = err {
-                warn!("Failed to load file {path}: {err}");
-            } else if opt.log_level > 1 {
-                info!("Added {path}");
+    report::sort(&mut report, opt.sort);
+    let now = report::now();
+    report
+        .into_iter()
+        .skip(opt.skip)
+        .take(opt.total)
+        .for_each(|r| {
+            // Whole files have no function or line.
+            let line = if r.func.is_empty() {
+                String::new()
+            } else {
+                r.line.to_string()
             };
+            let mut row = row![
+                r.file,
+                line,
+                r.func,
+                Fr->r.frequency(),
+                report::duration(now - r.first_seen),
+                report::date(r.last_changed),
+                Fr->format!("{:.1}", r.change_rate(now)),
+                Fr->format!("{:.1}", r.churn_rate(now)),
+            ];
+            if opt.issues {
+                row.add_cell(cell!(Fr->r.issues.len()));
+                row.add_cell(cell!(r.tickets(MAX_TICKETS)));
+            }
+            if show_submodules {
+                row.add_cell(cell!(r.submodule.unwrap_or_default()));
+            }
+            table.add_row(row);
         });
-        let pb = ProgressBar::new(0);
-
-        let parsers: Vec<(&str, Box<dyn Parser>)> = vec![
-            ("Go", Box::new(go_parser)),
-            ("Rust", Box::new(rust_parser)),
-            ("Lua", Box::new(lua_parser)),
-        ];
-
-        let mut report: Vec<(String, usize, String, usize)> = Vec::new();
-
-        for (name, mut parser) in parsers {
-            let res = parser.find_functions(&pb);
-            let res = match res {
-                Ok(r) => r,
-                Err(parser::Error::NoFilesAdded) => {
-                    debug!("Parser {name} didn't find any files");
-                    continue;
-                },
-                Err(parser::Error::ParseFile(msg)) => {
-                    warn!("Parser {name} encounter an error: {msg}");
-                    continue;
-                },
-                Err(err) => {
-                    debug!("Parser {name} encounter an error: {err}");
-                    return Err(err)?;
-                },
-            };
+    table.printstd();
+}
 
-            let start = Instant::now();
-            report.extend(
-                res.into_iter()
-                    .par_bridge()
-                    .map(|f| {
-                        pb.inc(1);
-                        (
-                            f.file.clone(),
-                            f.line,
-                            f.name.clone(),
-                            insighter.function_history(&f.file, &f.name).unwrap().len(),
-                        )
-                    })
-                    .collect::<Vec<(String, usize, String, usize)>>(),
-            );
-            debug!("Function hitory examination took {:?}", start.elapsed());
+#[cfg(test)]
+mod tests {
+    use hotspots_utilities::{cli_repo_init, commit_file};
+    use structopt::StructOpt;
+
+    use super::*;
+
+    // Unused when all the languages are disabled.
+    #[allow(dead_code)]
+    type DynError = Box<dyn std::error::Error>;
+
+    /// Returns the functions of the file, committed with each of the versions,
+    /// with their frequencies. The history of every function must be examined.
+    // Unused when all the languages are disabled.
+    #[allow(dead_code)]
+    fn frequencies(file: &str, versions: &[&str]) -> Result<Vec<(String, usize)>, DynError> {
+        let dir = cli_repo_init("sha1")?;
+        for (i, contents) in versions.iter().enumerate() {
+            commit_file(&dir, file, contents, &format!("commit {i}"))?;
         }
+        let root = dir.path().to_str().unwrap();
+        let opt = args::Opt::from_iter(["git-hotspots", "--strict", "--root", root]);
+        let (report, failures) = examine(&opt, &inspector(&opt)?)?;
+        assert!(failures.is_empty());
+        let mut res: Vec<_> = report.into_iter().map(|r| (r.func, r.freq)).collect();
+        res.sort();
+        Ok(res)
+    }
 
-        report.sort_by(|a, b| b.3.cmp(&a.3));
-        report
-            .into_iter()
-            .skip(opt.skip)
-            .take(opt.total)
-            .for_each(|(file, line, func, freq)| {
-                table.add_row(row![file, line, func, Fr->freq.to_string()]);
-            });
-        table.printstd();
-
-        pb.finish_with_message("done");
+    #[test]
+    #[cfg(feature = "javascript")]
+    fn examines_qualified_javascript_methods() -> Result<(), DynError> {
+        let v1 = "class Config {\n  #parse() {\n    return 1;\n  }\n}\n\n\
+                  const api = {\n  nested: {\n    load: () => 1,\n  },\n};\n";
+        let v2 = v1.replace("return 1", "return 2");
+        let res = frequencies("config.js", &[v1, &v2])?;
+        let want = [("Config.#parse", 2), ("api.nested.load", 1)];
+        assert_eq!(res, want.map(|(f, n)| (f.to_owned(), n)));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "cpp")]
+    fn examines_qualified_cpp_methods() -> Result<(), DynError> {
+        let v1 = "namespace app {\n\
+                  template <typename T>\n\
+                  T Box<T>::get() const {\n    return value;\n}\n\n\
+                  int *Config::find() {\n    return nullptr;\n}\n\
+                  }\n";
+        let v2 = v1.replace("return value", "return *value");
+        let res = frequencies("config.cpp", &[v1, &v2])?;
+        let want = [("app::Box<T>::get", 2), ("app::Config::find", 1)];
+        assert_eq!(res, want.map(|(f, n)| (f.to_owned(), n)));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "java")]
+    fn examines_java_overloads() -> Result<(), DynError> {
+        let v1 = "class Config {\n\
+                  void parse(String s) {\n        load(s);\n    }\n\n\
+                  void parse(String s, int[] xs) {\n        load(s);\n    }\n\
+                  }\n";
+        let v2 = v1.replacen("load(s);\n    }\n\n", "load(s, 0);\n    }\n\n", 1);
+        let v3 = v2.replace("load(s);", "load(s, xs);");
+        let v4 = v3.replace("load(s, xs);", "load(xs, s);");
+        let res = frequencies("Config.java", &[v1, &v2, &v3, &v4])?;
+        let want = [("Config.parse(String)", 2), ("Config.parse(String, int[])", 3)];
+        assert_eq!(res, want.map(|(f, n)| (f.to_owned(), n)));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "ruby")]
+    fn examines_ruby_methods() -> Result<(), DynError> {
+        let v1 = "module Ops\n  class Deploy\n    def run\n      1\n    end\n\n\
+                  def self.build\n      1\n    end\n  end\nend\n";
+        let v2 = v1.replacen("1", "2", 1);
+        let res = frequencies("lib/deploy.rb", &[v1, &v2])?;
+        let want = [("Ops::Deploy#run", 2), ("Ops::Deploy.build", 1)];
+        assert_eq!(res, want.map(|(f, n)| (f.to_owned(), n)));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "bash")]
+    fn examines_extensionless_scripts() -> Result<(), DynError> {
+        let v1 = "#!/usr/bin/env bash\ndeploy() {\n  echo 1\n}\n\ndeploy\n";
+        let v2 = v1.replace("echo 1", "echo 2");
+        let res = frequencies("bin/deploy", &[v1, &v2])?;
+        assert_eq!(res, [("deploy".to_owned(), 2)]);
         Ok(())
-    } else {
-        Err(anyhow::format_err!(
-            "No files found in the current directory"
-        ))
     }
 }
@@ -0,0 +1,207 @@
+//! This module holds the rows of the report and helpers for presenting them.
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Utc};
+use hotspots_insight::Commit;
+
+use crate::args::SortBy;
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// Row is a function in the report.
+pub struct Row {
+    pub file: String,
+    pub line: usize,
+    pub func: String,
+    pub freq: usize,
+    pub truncated: bool,
+    pub submodule: Option<String>,
+    /// When the function was first seen, as seconds since the Unix epoch.
+    pub first_seen: i64,
+    /// When the function was last changed, as seconds since the Unix epoch.
+    pub last_changed: i64,
+    /// Commits that changed the function, newest first.
+    pub commits: Vec<Commit>,
+    /// Distinct issue keys mentioned by the commits.
+    pub issues: Vec<String>,
+    /// Number of lines of the function added and removed by the commits.
+    pub churn: usize,
+}
+
+impl Row {
+    /// Returns a new row for the function with the given history.
+    pub fn new(file: String, line: usize, func: String, commits: Vec<Commit>) -> Self {
+        let timestamps = commits.iter().map(|c| c.timestamp);
+        Row {
+            file,
+            line,
+            func,
+            freq: commits.len(),
+            truncated: false,
+            submodule: None,
+            churn: commits.iter().map(|c| c.added + c.removed).sum(),
+            first_seen: timestamps.clone().min().unwrap_or_default(),
+            last_changed: timestamps.max().unwrap_or_default(),
+            commits,
+            issues: vec![],
+        }
+    }
+
+    /// Returns the timestamps of the commits that changed the function.
+    pub fn timestamps(&self) -> Vec<i64> {
+        self.commits.iter().map(|c| c.timestamp).collect()
+    }
+
+    /// Returns the first few issue keys, and how many more there are.
+    pub fn tickets(&self, max: usize) -> String {
+        let mut tickets = self.issues.iter().take(max).cloned().collect::<Vec<_>>();
+        if self.issues.len() > max {
+            tickets.push(format!("+{}", self.issues.len() - max));
+        }
+        tickets.join(", ")
+    }
+
+    /// Returns the number of changes per month since the function first
+    /// appeared until `now`.
+    pub fn change_rate(&self, now: i64) -> f64 {
+        self.freq as f64 / self.months(now)
+    }
+
+    /// Returns the number of changed lines per month since the function first
+    /// appeared until `now`.
+    pub fn churn_rate(&self, now: i64) -> f64 {
+        self.churn as f64 / self.months(now)
+    }
+
+    /// Returns the age of the function in months of 30 days, at least a day so
+    /// young functions don't get infinite rates.
+    fn months(&self, now: i64) -> f64 {
+        (now - self.first_seen).max(DAY) as f64 / (30 * DAY) as f64
+    }
+
+    /// Returns the frequency, marked with a + if the history is truncated.
+    pub fn frequency(&self) -> String {
+        if self.truncated {
+            format!("{}+", self.freq)
+        } else {
+            self.freq.to_string()
+        }
+    }
+}
+
+/// Sorts the rows by the given key, hottest, oldest, most recently changed,
+/// most troubled or fastest changing first.
+pub fn sort(rows: &mut [Row], by: SortBy) {
+    let now = now();
+    match by {
+        SortBy::Frequency => rows.sort_by_key(|r| Reverse(r.freq)),
+        SortBy::Age => rows.sort_by_key(|r| r.first_seen),
+        SortBy::LastChanged => rows.sort_by_key(|r| Reverse(r.last_changed)),
+        SortBy::Issues => rows.sort_by_key(|r| Reverse(r.issues.len())),
+        SortBy::ChangeRate => {
+            rows.sort_by(|a, b| b.change_rate(now).total_cmp(&a.change_rate(now)))
+        },
+        SortBy::ChurnRate => rows.sort_by(|a, b| b.churn_rate(now).total_cmp(&a.churn_rate(now))),
+    }
+}
+
+/// Returns the current time as seconds since the Unix epoch.
+pub fn now() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// Returns the date of the timestamp in the YYYY-MM-DD format.
+pub fn date(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Returns a short representation of the duration, for example 2y 3m.
+pub fn duration(secs: i64) -> String {
+    let days = secs.max(0) / DAY;
+    let (years, months) = (days / 365, (days % 365 / 30).min(11));
+    match (years, months) {
+        (0, 0) => format!("{days}d"),
+        (0, m) => format!("{m}m"),
+        (y, 0) => format!("{y}y"),
+        (y, m) => format!("{y}y {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(duration(-5), "0d");
+        assert_eq!(duration(3 * DAY + 5), "3d");
+        assert_eq!(duration(65 * DAY), "2m");
+        assert_eq!(duration(364 * DAY), "11m");
+        assert_eq!(duration(365 * DAY), "1y");
+        assert_eq!(duration(729 * DAY), "1y 11m");
+        assert_eq!(duration(800 * DAY), "2y 2m");
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(1_700_000_000), "2023-11-14");
+    }
+
+    #[test]
+    fn tickets() {
+        let mut row = Row::new("a".into(), 1, "a".into(), vec![]);
+        assert_eq!(row.tickets(2), "");
+        row.issues = vec!["A-1".into(), "A-2".into(), "B-3".into()];
+        assert_eq!(row.tickets(3), "A-1, A-2, B-3");
+        assert_eq!(row.tickets(2), "A-1, A-2, +1");
+    }
+
+    #[test]
+    fn sorts_rows() {
+        let commit = |timestamp| Commit {
+            timestamp,
+            ..Default::default()
+        };
+        let mut rows = vec![
+            Row::new("a".into(), 1, "a".into(), vec![commit(10), commit(20)]),
+            Row::new("b".into(), 1, "b".into(), vec![commit(5)]),
+            Row::new("c".into(), 1, "c".into(), vec![commit(30), commit(15), commit(12)]),
+        ];
+        let names = |rows: &[Row]| rows.iter().map(|r| r.func.clone()).collect::<Vec<_>>();
+
+        sort(&mut rows, SortBy::Frequency);
+        assert_eq!(names(&rows), ["c", "a", "b"]);
+        sort(&mut rows, SortBy::Age);
+        assert_eq!(names(&rows), ["b", "a", "c"]);
+        sort(&mut rows, SortBy::LastChanged);
+        assert_eq!(names(&rows), ["c", "a", "b"]);
+        rows[2].issues = vec!["A-1".into()];
+        sort(&mut rows, SortBy::Issues);
+        assert_eq!(names(&rows), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn rates() {
+        let now = now();
+        let commit = |days: i64, added| Commit {
+            timestamp: now - days * DAY,
+            added,
+            removed: 1,
+            ..Default::default()
+        };
+        let old = Row::new("a".into(), 1, "a".into(), vec![commit(10, 3), commit(60, 5)]);
+        assert_eq!(old.change_rate(now), 1.0);
+        assert_eq!(old.churn_rate(now), 5.0);
+        let new = Row::new("b".into(), 1, "b".into(), vec![Commit { removed: 0, ..commit(0, 0) }]);
+        assert_eq!(new.change_rate(now), 30.0);
+
+        let mut rows = vec![old, new];
+        sort(&mut rows, SortBy::ChangeRate);
+        assert_eq!(rows[0].func, "b");
+        sort(&mut rows, SortBy::ChurnRate);
+        assert_eq!(rows[0].func, "a");
+    }
+}
@@ -0,0 +1,44 @@
+//! This module prints the commits that changed a function.
+use std::path::Path;
+
+use anyhow::Result;
+use hotspots_insight::{Function, Inspector};
+use prettytable::{format, Table};
+
+use crate::args::Opt;
+use crate::report;
+
+/// Prints the commits that changed the function, newest first. With `patch`,
+/// each commit is followed by the diff of the function. The file is relative
+/// to the root.
+pub fn print(
+    opt: &Opt,
+    insighter: &Inspector,
+    file: &str,
+    function: Function,
+    patch: bool,
+) -> Result<()> {
+    let path = Path::new(&opt.root).join(file);
+    let path = path.to_str().unwrap_or(file);
+
+    if patch {
+        for p in insighter.function_patches(path, function)? {
+            let c = &p.commit;
+            println!("commit {}", c.hash);
+            println!("Author: {}", c.author);
+            println!("Date:   {}", report::date(c.timestamp));
+            println!("\n    {}\n", c.subject);
+            println!("{}\n", p.diff);
+        }
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![bFg->"HASH", bFg->"DATE", bFg->"AUTHOR", bFg->"SUBJECT"]);
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    for c in insighter.function_history(path, function)? {
+        table.add_row(row![c.hash, report::date(c.timestamp), c.author, c.subject]);
+    }
+    table.printstd();
+    Ok(())
+}
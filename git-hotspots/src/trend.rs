@@ -0,0 +1,108 @@
+//! This module prints how often functions changed in each period of time.
+use std::cmp::Reverse;
+
+use anyhow::Result;
+use hotspots_insight::trend::{self, Bucket, Period};
+use prettytable::{format, Table};
+use serde::Serialize;
+
+use crate::args::Opt;
+use crate::report::{self, Row};
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Series is the trend of a function.
+#[derive(Serialize)]
+struct Series {
+    file: String,
+    line: usize,
+    function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    submodule: Option<String>,
+    total: usize,
+    buckets: Vec<Point>,
+}
+
+/// Point is the number of commits in a period starting at the given date.
+#[derive(Serialize)]
+struct Point {
+    start: String,
+    count: usize,
+}
+
+/// Prints the trend of the functions that changed the most in the given
+/// periods, as a table of sparklines or as JSON.
+pub fn print(opt: &Opt, report: Vec<Row>, period: Period, count: usize, json: bool) -> Result<()> {
+    let now = report::now();
+    let mut series: Vec<(Row, Vec<Bucket>)> = report
+        .into_iter()
+        .map(|r| {
+            let buckets = trend::trend(&r.timestamps(), period, count, now);
+            (r, buckets)
+        })
+        .collect();
+    series.sort_by_key(|(_, b)| Reverse(total(b)));
+    let series = series.into_iter().skip(opt.skip).take(opt.total);
+
+    if json {
+        let series: Vec<Series> = series
+            .map(|(r, buckets)| Series {
+                total: total(&buckets),
+                file: r.file,
+                line: r.line,
+                function: r.func,
+                submodule: r.submodule,
+                buckets: buckets
+                    .into_iter()
+                    .map(|b| Point {
+                        start: report::date(b.start),
+                        count: b.count,
+                    })
+                    .collect(),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&series)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![bFg->"FILE", bFg->"LINE", bFg->"FUNCTION", bFg->"TREND", bFg->"TOTAL"]);
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    for (r, buckets) in series {
+        table.add_row(row![r.file, r.line, r.func, sparkline(&buckets), Fr->total(&buckets)]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+fn total(buckets: &[Bucket]) -> usize {
+    buckets.iter().map(|b| b.count).sum()
+}
+
+/// Returns a sparkline of the counts, scaled to the largest one. Empty
+/// periods are shown as spaces.
+fn sparkline(buckets: &[Bucket]) -> String {
+    let max = buckets.iter().map(|b| b.count).max().unwrap_or_default();
+    buckets
+        .iter()
+        .map(|b| match b.count {
+            0 => ' ',
+            n => SPARKS[(n * (SPARKS.len() - 1) + max - 1) / max],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparklines() {
+        let buckets = [0, 1, 4, 8]
+            .into_iter()
+            .map(|count| Bucket { start: 0, count })
+            .collect::<Vec<_>>();
+        assert_eq!(sparkline(&buckets), " ▂▅█");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
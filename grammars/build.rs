@@ -0,0 +1,23 @@
+//! This crate contains the build script compiling the vendored grammars of the
+//! enabled features.
+use std::env;
+use std::path::Path;
+
+fn main() {
+    for lang in ["ruby", "bash"] {
+        if env::var_os(format!("CARGO_FEATURE_{}", lang.to_uppercase())).is_none() {
+            continue;
+        }
+        let dir = Path::new(lang);
+        cc::Build::new()
+            .std("c11")
+            .include(dir)
+            .flag_if_supported("-Wno-unused-parameter")
+            .flag_if_supported("-Wno-unused-value")
+            .flag_if_supported("-Wno-unused-but-set-variable")
+            .file(dir.join("parser.c"))
+            .file(dir.join("scanner.c"))
+            .compile(&format!("tree-sitter-{lang}"));
+        println!("cargo:rerun-if-changed={lang}");
+    }
+}
@@ -0,0 +1,52 @@
+//! This crate builds the tree-sitter grammars that are vendored instead of
+//! being taken from their own crates, as their published crates can't be
+//! built together with the other grammars. Each grammar is behind a cargo
+//! feature of the same name.
+#![warn(missing_docs)]
+use tree_sitter::Language;
+
+extern "C" {
+    #[cfg(feature = "ruby")]
+    fn tree_sitter_ruby() -> Language;
+    #[cfg(feature = "bash")]
+    fn tree_sitter_bash() -> Language;
+}
+
+/// Returns the tree-sitter language of Ruby.
+#[cfg(feature = "ruby")]
+pub fn ruby() -> Language {
+    // SAFETY: the function is defined by the compiled grammar, and returns a
+    // pointer to its static language.
+    unsafe { tree_sitter_ruby() }
+}
+
+/// Returns the tree-sitter language of Bash.
+#[cfg(feature = "bash")]
+pub fn bash() -> Language {
+    // SAFETY: the function is defined by the compiled grammar, and returns a
+    // pointer to its static language.
+    unsafe { tree_sitter_bash() }
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::Parser;
+
+    #[test]
+    #[cfg(feature = "ruby")]
+    fn parses_ruby() {
+        let mut parser = Parser::new();
+        parser.set_language(super::ruby()).unwrap();
+        let tree = parser.parse("def a\n  1\nend\n", None).unwrap();
+        assert_eq!(tree.root_node().child(0).unwrap().kind(), "method");
+    }
+
+    #[test]
+    #[cfg(feature = "bash")]
+    fn parses_bash() {
+        let mut parser = Parser::new();
+        parser.set_language(super::bash()).unwrap();
+        let tree = parser.parse("a() {\n  echo 1\n}\n", None).unwrap();
+        assert_eq!(tree.root_node().child(0).unwrap().kind(), "function_definition");
+    }
+}
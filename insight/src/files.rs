@@ -0,0 +1,83 @@
+//! This module counts the commits and churn of whole files, for files whose
+//! functions can't be found.
+use std::collections::HashMap;
+use std::path::Path;
+use std::str;
+
+use grep_matcher::Matcher;
+
+use super::{Commit, Error, Inspector, LOG_FORMAT};
+
+impl Inspector {
+    /// Returns the commits that changed each file, newest first, with the
+    /// lines added and removed in the file. The history is read in a single
+    /// pass, and the history of loaded submodules is read in their own
+    /// repository. Paths are joined with the path of the repository.
+    pub fn file_history(&self) -> Result<HashMap<String, Vec<Commit>>, Error> {
+        let output = self
+            .git
+            .run(["log", LOG_FORMAT, "--numstat", "--no-renames", "--relative"])?;
+        let mut files = self.numstat(str::from_utf8(&output.stdout)?)?;
+        for submodule in &self.submodules {
+            files.extend(submodule.inspector().file_history()?);
+        }
+        Ok(files)
+    }
+
+    /// Returns the commits in the output of `git log --numstat` for each file.
+    /// Binary files count as changed without lines.
+    fn numstat(&self, input: &str) -> Result<HashMap<String, Vec<Commit>>, Error> {
+        let mut files: HashMap<String, Vec<Commit>> = HashMap::new();
+        let mut caps = self.matcher.new_captures().map_err(std::io::Error::from)?;
+        let mut commit: Option<Commit> = None;
+        for line in input.lines() {
+            if let Some(header) = self.header(line, &mut caps) {
+                commit = Some(header);
+                continue;
+            }
+            let (Some(commit), Some((added, rest))) = (&commit, line.split_once('\t')) else {
+                continue;
+            };
+            let Some((removed, path)) = rest.split_once('\t') else {
+                continue;
+            };
+            let path = Path::new(self.git.path()).join(path);
+            files
+                .entry(path.to_string_lossy().into_owned())
+                .or_default()
+                .push(Commit {
+                    added: added.parse().unwrap_or_default(),
+                    removed: removed.parse().unwrap_or_default(),
+                    ..commit.clone()
+                });
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    type DynError = Box<dyn std::error::Error>;
+
+    #[test]
+    fn counts_files() -> Result<(), DynError> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        hotspots_utilities::commit_file(&dir, "ci.yaml", "a: 1\n", "first")?;
+        hotspots_utilities::commit_file(&dir, "ci.yaml", "a: 2\nb: 3\n", "second")?;
+        hotspots_utilities::commit_file(&dir, "run.sh", "echo\n", "third")?;
+        let root = dir.path().to_str().unwrap();
+        let inspector = Inspector::new(root)?;
+
+        let files = inspector.file_history()?;
+        assert_that!(files).has_length(2);
+        let yaml = &files[&Path::new(root).join("ci.yaml").to_string_lossy().into_owned()];
+        let churn = yaml.iter().map(|c| (c.added, c.removed)).collect::<Vec<_>>();
+        assert_that!(churn).is_equal_to(vec![(2, 1), (1, 0)]);
+        assert_that!(yaml[0].subject).is_equal_to("second".to_owned());
+        Ok(())
+    }
+}
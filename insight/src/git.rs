@@ -0,0 +1,276 @@
+//! This module runs git commands in an environment that produces the same
+//! output regardless of the user's configuration.
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use std::{env, io};
+
+use wait_timeout::ChildExt;
+
+use super::Error;
+
+/// Default time each git command is allowed to run.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Configurations that change the output of git commands, pinned to the
+/// values we can parse.
+const PINNED_CONFIG: &[&str] = &[
+    "color.diff=never",
+    "color.ui=never",
+    "core.quotePath=false",
+    "diff.mnemonicPrefix=false",
+    "diff.noprefix=false",
+    "format.pretty=medium",
+    "i18n.logOutputEncoding=UTF-8",
+    "log.abbrevCommit=false",
+    "log.decorate=false",
+    "log.showSignature=false",
+];
+
+/// Git runs git commands in the repository.
+#[derive(Debug, Clone)]
+pub struct Git {
+    binary: String,
+    path: String,
+    timeout: Duration,
+    env: Vec<(&'static str, PathBuf)>,
+}
+
+impl Git {
+    /// Returns a new Git that runs in the given path. The GIT_DIR and
+    /// GIT_WORK_TREE variables of the environment are honoured.
+    pub fn new(path: &str) -> Self {
+        Git {
+            binary: String::from("git"),
+            path: String::from(path),
+            timeout: DEFAULT_TIMEOUT,
+            env: ["GIT_DIR", "GIT_WORK_TREE"]
+                .into_iter()
+                .filter_map(|var| Some((var, absolute_env(var)?)))
+                .collect(),
+        }
+    }
+
+    /// Sets the git binary to run.
+    pub fn with_binary(mut self, binary: &str) -> Self {
+        self.binary = String::from(binary);
+        self
+    }
+
+    /// Sets the time each command is allowed to run before it is killed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns a Git with the same settings that runs in the given path, like
+    /// a submodule. The GIT_DIR and GIT_WORK_TREE variables are not inherited,
+    /// so the commands run in the repository of the path.
+    pub fn at(&self, path: &str) -> Self {
+        Git {
+            path: String::from(path),
+            env: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// Returns the path the commands run in.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a Command for the given arguments. The locale is pinned, and
+    /// pager, colours, external diff tools and signatures are disabled. The
+    /// GIT_DIR and GIT_WORK_TREE variables are only set when inherited.
+    pub fn command<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut cmd = Command::new(&self.binary);
+        cmd.arg("--no-pager");
+        for config in PINNED_CONFIG {
+            cmd.args(["-c", config]);
+        }
+        cmd.args(args)
+            .current_dir(&self.path)
+            .env("LC_ALL", "C")
+            .env("LANG", "C")
+            .env("GIT_PAGER", "cat")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env_remove("LANGUAGE")
+            .env_remove("GIT_EXTERNAL_DIFF")
+            .env_remove("GIT_DIR")
+            .env_remove("GIT_WORK_TREE");
+        for (var, path) in &self.env {
+            cmd.env(var, path);
+        }
+        cmd
+    }
+
+    /// Runs the command with the given arguments and collects its output. It
+    /// returns an error if the command doesn't finish in time.
+    pub fn run<I, S>(&self, args: I) -> Result<Output, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let args: Vec<S> = args.into_iter().collect();
+        let mut child = self
+            .command(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let status = match child.wait_timeout(self.timeout)? {
+            Some(status) => status,
+            None => {
+                child.kill()?;
+                child.wait()?;
+                return Err(Error::Timeout {
+                    args: args
+                        .iter()
+                        .map(|a| a.as_ref().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    timeout: self.timeout,
+                });
+            },
+        };
+
+        Ok(Output {
+            status,
+            stdout: join_pipe(stdout)?,
+            stderr: join_pipe(stderr)?,
+        })
+    }
+}
+
+/// Returns the value of the environment variable as an absolute path, as the
+/// commands don't run in the current directory.
+fn absolute_env(var: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(env::var_os(var)?);
+    if path.is_relative() {
+        env::current_dir().ok().map(|dir| dir.join(path))
+    } else {
+        Some(path)
+    }
+}
+
+/// Reads the pipe in the background so the child doesn't block on a full
+/// buffer while we wait for it.
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf)?;
+        }
+        Ok(buf)
+    })
+}
+
+fn join_pipe(handle: JoinHandle<io::Result<Vec<u8>>>) -> Result<Vec<u8>, Error> {
+    handle
+        .join()
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "can't read the output"))?
+        .map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str;
+
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    type DynError = Box<dyn std::error::Error>;
+
+    #[test]
+    fn pins_configuration() -> Result<(), DynError> {
+        let (dir, repo) = hotspots_utilities::repo_init();
+        let mut config = repo.config()?;
+        config.set_bool("log.showSignature", true)?;
+        config.set_str("color.ui", "always")?;
+        config.set_str("color.diff", "always")?;
+
+        let git = Git::new(dir.path().to_str().unwrap());
+        for (key, want) in [
+            ("log.showSignature", "false"),
+            ("color.ui", "never"),
+            ("color.diff", "never"),
+        ] {
+            let output = git.run(["config", key])?;
+            assert_that!(str::from_utf8(&output.stdout)?.trim()).is_equal_to(want);
+        }
+
+        hotspots_utilities::commit_file(&dir, "a.txt", "a", "second")?;
+        let output = git.run(["log", "-p"])?;
+        let log = str::from_utf8(&output.stdout)?;
+        assert_that!(log).contains("@@ -0,0 +1 @@");
+        assert_that!(log.contains('\x1b')).is_false();
+        Ok(())
+    }
+
+    /// Writes an executable script that stands in for the git binary.
+    #[cfg(unix)]
+    fn script(body: &str) -> Result<(tempfile::TempDir, String), DynError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new()?;
+        let path = dir.path().join("git");
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n"))?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        let path = path.to_str().unwrap().to_owned();
+        Ok((dir, path))
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pins_environment() -> Result<(), DynError> {
+        let (dir, binary) = script(r#"echo "$LC_ALL $GIT_PAGER $1""#)?;
+        let git = Git::new(dir.path().to_str().unwrap()).with_binary(&binary);
+        let output = git.run(["log"])?;
+        assert_that!(str::from_utf8(&output.stdout)?.trim()).is_equal_to("C cat --no-pager");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn submodules_ignore_git_dir() -> Result<(), DynError> {
+        let (dir, binary) = script(r#"echo "$GIT_DIR""#)?;
+        let mut git = Git::new(dir.path().to_str().unwrap()).with_binary(&binary);
+        git.env = vec![("GIT_DIR", PathBuf::from("/elsewhere/.git"))];
+        let output = git.run(["log"])?;
+        assert_that!(str::from_utf8(&output.stdout)?.trim()).is_equal_to("/elsewhere/.git");
+
+        let output = git.at(dir.path().to_str().unwrap()).run(["log"])?;
+        assert_that!(str::from_utf8(&output.stdout)?.trim()).is_equal_to("");
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_binary() {
+        let git = Git::new(".").with_binary("/nonexistent/git");
+        let res = git.run(["status"]);
+        assert!(matches!(res, Err(Error::IO(_))));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn times_out() -> Result<(), DynError> {
+        let (dir, binary) = script("exec sleep 5")?;
+        let git = Git::new(dir.path().to_str().unwrap())
+            .with_binary(&binary)
+            .with_timeout(Duration::from_millis(50));
+        let res = git.run(["log"]);
+        assert!(matches!(res, Err(Error::Timeout { .. })));
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! This module counts how many commits changed each line of a function, by
+//! following its lines back through the diffs of its history.
+use std::str;
+
+use super::patch::{self, Origin, Patch};
+use super::{Error, Function, Inspector};
+
+/// Line is a line of a function and the number of commits that changed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Number of the line in the file at HEAD.
+    pub number: usize,
+    /// Content of the line.
+    pub text: String,
+    /// Number of commits that added or changed the line.
+    pub changes: usize,
+}
+
+impl Inspector {
+    /// Returns the lines of the function, each with the number of commits
+    /// that added or changed it. A line replaced by a commit is followed back
+    /// to the line it replaced, so its earlier changes are counted too.
+    pub fn line_heat<'a>(
+        &self,
+        filename: &str,
+        func: impl Into<Function<'a>>,
+    ) -> Result<Vec<Line>, Error> {
+        let func = func.into();
+        if let Some((submodule, path)) = self.submodule(filename) {
+            return submodule.inspector().line_heat(&path, func);
+        }
+        let patches = self.function_patches(filename, func)?;
+        let Some(newest) = patches.first() else {
+            return Ok(vec![]);
+        };
+        let Some(start) = newest.hunks.first().map(|h| h.new_start) else {
+            return Ok(vec![]);
+        };
+        let head = self.head_line(filename, &newest.commit.hash, start)?;
+        let offset = head as isize - start as isize;
+
+        let mut lines: Vec<Line> = newest
+            .hunks
+            .iter()
+            .flat_map(|h| h.new_lines())
+            .map(|(number, text)| Line {
+                number: (number as isize + offset) as usize,
+                text: text.to_owned(),
+                changes: 0,
+            })
+            .collect();
+        let mut positions: Vec<Option<usize>> = newest
+            .hunks
+            .iter()
+            .flat_map(|h| h.new_lines())
+            .map(|(number, _)| Some(number - start))
+            .collect();
+        for patch in &patches {
+            for (line, position) in lines.iter_mut().zip(positions.iter_mut()) {
+                *position = position.and_then(|p| follow(patch, p, &mut line.changes));
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Returns the line at HEAD of the given line of the file at the commit.
+    fn head_line(&self, filename: &str, hash: &str, line: usize) -> Result<usize, Error> {
+        let output = self
+            .git
+            .run(["diff", "--no-ext-diff", "HEAD", hash, "--", filename])?;
+        let hunks = patch::hunks(str::from_utf8(&output.stdout)?.lines());
+        match patch::origin(&hunks, line) {
+            Origin::Unchanged(n) | Origin::Changed(n) => Ok(n),
+            Origin::Added => Ok(line),
+        }
+    }
+}
+
+/// Returns the position of the line in the function before the patch, relative
+/// to the start of the function, and counts the change if the patch added or
+/// changed the line. It returns None when the line didn't exist before.
+fn follow(patch: &Patch, position: usize, changes: &mut usize) -> Option<usize> {
+    let hunk = patch.hunks.first()?;
+    match patch::origin(&patch.hunks, hunk.new_start + position) {
+        Origin::Unchanged(n) => n.checked_sub(hunk.old_start),
+        Origin::Changed(n) => {
+            *changes += 1;
+            n.checked_sub(hunk.old_start)
+        },
+        Origin::Added => {
+            *changes += 1;
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    type DynError = Box<dyn std::error::Error>;
+
+    #[test]
+    fn counts_changes() -> Result<(), DynError> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        let v1 = "fn a() {\n    1\n    2\n}\n";
+        let v2 = "fn a() {\n    1\n    3\n}\n";
+        let v3 = "// A comment.\nfn a() {\n    1\n    4\n    5\n}\n";
+        hotspots_utilities::commit_file(&dir, "lib.rs", v1, "first")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", v2, "second")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", v3, "third")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", &format!("//\n{v3}"), "fourth")?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+
+        let lines = inspector.line_heat("lib.rs", "a")?;
+        let heat = lines
+            .iter()
+            .map(|l| (l.number, l.text.as_str(), l.changes))
+            .collect::<Vec<_>>();
+        assert_that!(heat).is_equal_to(vec![
+            (3, "fn a() {", 1),
+            (4, "    1", 1),
+            (5, "    4", 3),
+            (6, "    5", 1),
+            (7, "}", 1),
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! This module extracts issue-tracker keys, like `PAY-1234`, from commit
+//! messages so functions can be linked to the tickets that touched them.
+use std::collections::{BTreeSet, HashMap};
+use std::str;
+
+use grep_matcher::Matcher;
+use grep_regex::RegexMatcher;
+
+use super::{Commit, Error, Inspector};
+
+/// Default pattern for issue keys, which matches keys like `PAY-1234`.
+pub const DEFAULT_PATTERN: &str = r"\b[A-Z][A-Z0-9]+-[0-9]+\b";
+
+/// Issues maps commits to the issue keys mentioned in their messages.
+#[derive(Debug, Default)]
+pub struct Issues {
+    keys: HashMap<String, Vec<String>>,
+}
+
+impl Issues {
+    /// Returns the distinct issue keys mentioned by the commits, sorted.
+    pub fn keys(&self, commits: &[Commit]) -> Vec<String> {
+        commits
+            .iter()
+            .filter_map(|c| self.keys.get(&c.hash))
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Adds the keys matching the pattern in the output of `git log` with
+    /// records of a hash and a message separated by NUL and terminated by the
+    /// record separator character.
+    fn extend(&mut self, matcher: &RegexMatcher, log: &str) {
+        for record in log.split('\x1e') {
+            let Some((hash, message)) = record.trim_start().split_once('\0') else {
+                continue;
+            };
+            let mut keys: Vec<String> = vec![];
+            let _ = matcher.find_iter(message.as_bytes(), |m| {
+                keys.push(message[m].to_owned());
+                true
+            });
+            if !keys.is_empty() {
+                self.keys.insert(hash.to_owned(), keys);
+            }
+        }
+    }
+}
+
+impl Inspector {
+    /// Reads the messages of all commits in the history of the repository, and
+    /// of loaded submodules, and collects the issue keys matching the pattern.
+    pub fn issues(&self, pattern: &str) -> Result<Issues, Error> {
+        let matcher = RegexMatcher::new(pattern)?;
+        let mut issues = Issues::default();
+        self.collect_issues(&matcher, &mut issues)?;
+        Ok(issues)
+    }
+
+    fn collect_issues(&self, matcher: &RegexMatcher, issues: &mut Issues) -> Result<(), Error> {
+        let output = self.git.run(["log", "--format=%H%x00%B%x1e"])?;
+        issues.extend(matcher, str::from_utf8(&output.stdout)?);
+        for submodule in &self.submodules {
+            submodule.inspector().collect_issues(matcher, issues)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    type DynError = Box<dyn std::error::Error>;
+
+    fn commit(hash: &str) -> Commit {
+        Commit {
+            hash: hash.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extracts_keys() -> Result<(), DynError> {
+        let matcher = RegexMatcher::new(DEFAULT_PATTERN)?;
+        let mut issues = Issues::default();
+        let log = "aaa\0Fix PAY-1234\n\nAlso fixes PAY-99 and pay-1.\n\x1e\n\
+                   bbb\0PAY-1234 follow up\n\x1e\nccc\0No ticket\n\x1e\n";
+        issues.extend(&matcher, log);
+
+        let keys = issues.keys(&[commit("aaa"), commit("bbb"), commit("ccc")]);
+        assert_that!(keys).is_equal_to(vec!["PAY-1234".to_owned(), "PAY-99".to_owned()]);
+        assert_that!(issues.keys(&[commit("ccc"), commit("ddd")])).is_empty();
+        Ok(())
+    }
+
+    #[test]
+    fn custom_pattern() -> Result<(), DynError> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", "fn a() {}\n", "Fix #12 and #7")?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+        let commits = inspector.function_history("lib.rs", "a")?;
+
+        let issues = inspector.issues(r"#[0-9]+")?;
+        let want = vec!["#12".to_owned(), "#7".to_owned()];
+        assert_that!(issues.keys(&commits)).is_equal_to(want);
+        Ok(())
+    }
+}
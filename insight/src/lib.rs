@@ -1,20 +1,115 @@
 //! This crate is used to get the history of functions and methods in a git
 //! repository.
+pub mod files;
+pub mod git;
+pub mod heat;
+pub mod issues;
+pub mod patch;
+pub mod submodule;
+pub mod trend;
 
-use std::process::Command;
-use std::{io, str};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use std::{fs, io, str};
 
 use grep_matcher::{Captures, Matcher};
-use grep_regex::RegexMatcher;
-use grep_searcher::sinks::UTF8;
-use grep_searcher::Searcher;
+use grep_regex::{RegexCaptures, RegexMatcher};
 use thiserror::Error as TError;
 
+pub use crate::git::Git;
+use crate::patch::{Hunk, Origin};
+pub use crate::patch::Patch;
+pub use crate::submodule::Submodule;
+
 /// Inspector interrogates the git repository for history of functions and
 /// methods.
 pub struct Inspector {
     matcher: RegexMatcher,
-    path: String,
+    git: Git,
+    format: ObjectFormat,
+    shallow: Vec<String>,
+    partial: bool,
+    submodules: Vec<Submodule>,
+    head_diffs: Mutex<HashMap<String, Vec<Hunk>>>,
+}
+
+/// Commit is a commit that changed a function.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Commit {
+    /// Full hash of the commit.
+    pub hash: String,
+    /// Author date as seconds since the Unix epoch.
+    pub timestamp: i64,
+    /// Name of the author.
+    pub author: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Number of lines of the function added by the commit.
+    pub added: usize,
+    /// Number of lines of the function removed by the commit.
+    pub removed: usize,
+}
+
+/// Function is a function in a file whose history is followed. Its lines are
+/// followed when they are known, otherwise git finds it by matching its name
+/// as a regex, like `git log -L :<name>:<file>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Function<'a> {
+    /// Name of the function, also used in the errors.
+    pub name: &'a str,
+    /// First and last lines of the function in the file.
+    pub lines: Option<(usize, usize)>,
+}
+
+impl<'a> Function<'a> {
+    /// Returns the function at the given lines of the file. The name is only
+    /// used in the errors, so it can be any label.
+    pub fn at(name: &'a str, start: usize, end: usize) -> Self {
+        Function {
+            name,
+            lines: Some((start, end)),
+        }
+    }
+
+    /// Returns the argument of `git log -L` for the function in the file.
+    fn range(&self, filename: &str) -> String {
+        match self.lines {
+            Some((start, end)) => format!("{start},{end}:{filename}"),
+            None => format!(":{}:{filename}", self.name),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Function<'a> {
+    fn from(name: &'a str) -> Self {
+        Function { name, lines: None }
+    }
+}
+
+/// Format of the commit header lines in the logs, which are parsed by the
+/// Inspector's matcher.
+const LOG_FORMAT: &str = "--format=commit %H%x09%at%x09%an%x09%s";
+
+/// Hash algorithm of the objects in a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectFormat {
+    /// Repositories with 40 characters long hashes.
+    Sha1,
+    /// Repositories initialised with `--object-format=sha256`, with 64
+    /// characters long hashes.
+    Sha256,
+}
+
+impl ObjectFormat {
+    /// Returns the length of a full hash in hex.
+    pub fn hex_len(&self) -> usize {
+        match self {
+            ObjectFormat::Sha1 => 40,
+            ObjectFormat::Sha256 => 64,
+        }
+    }
 }
 
 /// Error enumerates all errors for this application.
@@ -35,56 +130,237 @@ pub enum Error {
     /// When the path is not a git repository.
     #[error("Not a git directory")]
     NotGitRepo,
+
+    /// When git fails to produce the history of a function.
+    #[error("Can't get history of {func} in {file}: {stderr}")]
+    History {
+        /// File containing the function.
+        file: String,
+        /// Name of the function.
+        func: String,
+        /// What git wrote to its stderr.
+        stderr: String,
+    },
+
+    /// When a git command doesn't finish in time.
+    #[error("git {args} timed out after {timeout:?}")]
+    Timeout {
+        /// Arguments of the git command.
+        args: String,
+        /// Time the command was allowed to run.
+        timeout: Duration,
+    },
 }
 
 impl Inspector {
     /// Returns an error if the path is not a valid repository.
     pub fn new(path: &str) -> Result<Self, Error> {
-        let output = Command::new("git")
-            .args(["rev-parse", "--is-inside-work-tree"])
-            .current_dir(path)
-            .output()?;
+        Self::with_git(Git::new(path))
+    }
+
+    /// Returns an Inspector that runs git commands with the given Git. It
+    /// returns an error if the path of the Git is not a valid repository.
+    pub fn with_git(git: Git) -> Result<Self, Error> {
+        let output = git.run(["rev-parse", "--is-inside-work-tree"])?;
         if output.status.success() {
+            let format = object_format(&git)?;
+            let pattern = format!(
+                r"^commit ([0-9a-f]{{{}}})(?:\t(\d+)\t([^\t]*)\t(.*))?",
+                format.hex_len()
+            );
             Ok(Inspector {
-                matcher: RegexMatcher::new(r"^commit (.{40})")?,
-                path: String::from(path),
+                matcher: RegexMatcher::new(&pattern)?,
+                format,
+                shallow: shallow_boundary(&git)?,
+                partial: is_partial(&git)?,
+                submodules: vec![],
+                head_diffs: Mutex::default(),
+                git,
             })
         } else {
             Err(Error::NotGitRepo)
         }
     }
 
-    /// Returns the commits that the function by func_name appears for the
-    /// filename from beginning of the repository.
-    pub fn function_history(&self, filename: &str, func_name: &str) -> Result<Vec<String>, Error> {
-        let input = format!(":{func_name}:{filename}");
-        let output = Command::new("git")
-            .args(["log", "-L", &input])
-            .current_dir(&self.path)
-            .output()?;
-        self.commits(str::from_utf8(&output.stdout)?)
+    /// Returns the hash algorithm of the repository.
+    pub fn object_format(&self) -> ObjectFormat {
+        self.format
+    }
+
+    /// Returns true if the repository is a shallow clone. The history of
+    /// functions stops at the shallow boundary.
+    pub fn is_shallow(&self) -> bool {
+        !self.shallow.is_empty()
     }
 
-    fn commits(&self, input: &str) -> Result<Vec<String>, Error> {
-        let mut matches: Vec<String> = vec![];
-        Searcher::new().search_slice(
-            &self.matcher,
-            input.as_bytes(),
-            UTF8(|_, line| {
-                let mut caps = self.matcher.new_captures()?;
-                match self.matcher.captures(line.as_bytes(), &mut caps) {
-                    Ok(true) => {
-                        matches.push(line[caps.get(1).unwrap()].to_owned());
-                        Ok(true)
-                    },
-                    _ => Ok(false),
-                }
-            }),
-        )?;
-        Ok(matches)
+    /// Returns true if the repository is a partial clone, which means some
+    /// objects may be fetched lazily while examining the history.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    /// Returns true if the history of the file reaches the shallow boundary of
+    /// the repository it belongs to, which means older changes are missing.
+    pub fn truncated(&self, filename: &str, commits: &[Commit]) -> bool {
+        if let Some((submodule, path)) = self.submodule(filename) {
+            return submodule.inspector().truncated(&path, commits);
+        }
+        commits.iter().any(|c| self.shallow.contains(&c.hash))
+    }
+
+    /// Returns the commits that the function appears for the filename from
+    /// beginning of the repository, newest first. When submodules are loaded,
+    /// the history of files inside them is queried in the submodule.
+    pub fn function_history<'a>(
+        &self,
+        filename: &str,
+        func: impl Into<Function<'a>>,
+    ) -> Result<Vec<Commit>, Error> {
+        let patches = self.function_patches(filename, func)?;
+        Ok(patches.into_iter().map(|p| p.commit).collect())
+    }
+
+    /// Returns the commits that changed the function together with their
+    /// diffs of the function, newest first.
+    pub fn function_patches<'a>(
+        &self,
+        filename: &str,
+        func: impl Into<Function<'a>>,
+    ) -> Result<Vec<Patch>, Error> {
+        let func = func.into();
+        if let Some((submodule, path)) = self.submodule(filename) {
+            return submodule.inspector().function_patches(&path, func);
+        }
+        let input = self.at_head(filename, func)?.range(filename);
+        let output = self.git.run(["log", LOG_FORMAT, "--no-ext-diff", "-L", &input])?;
+        if !output.status.success() {
+            return Err(Error::History {
+                file: filename.to_owned(),
+                func: func.name.to_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            });
+        }
+        self.patches(str::from_utf8(&output.stdout)?)
+    }
+
+    /// Returns the function with its lines in the working tree mapped to the
+    /// file at HEAD, which is where `git log -L` follows them. A function whose
+    /// first line is not at HEAD yet is found by its name.
+    fn at_head<'a>(&self, filename: &str, func: Function<'a>) -> Result<Function<'a>, Error> {
+        let Some((start, end)) = func.lines else {
+            return Ok(func);
+        };
+        let cached = self
+            .head_diffs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(filename)
+            .cloned();
+        let hunks = match cached {
+            Some(hunks) => hunks,
+            None => {
+                let output = self
+                    .git
+                    .run(["diff", "--no-ext-diff", "HEAD", "--", filename])?;
+                let hunks = patch::hunks(str::from_utf8(&output.stdout)?.lines());
+                self.head_diffs
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(filename.to_owned(), hunks.clone());
+                hunks
+            },
+        };
+        let head = |line| match patch::origin(&hunks, line) {
+            Origin::Unchanged(n) | Origin::Changed(n) => Some(n),
+            Origin::Added => None,
+        };
+        match (head(start), (start..=end).rev().find_map(head)) {
+            (Some(start), Some(end)) => Ok(Function::at(func.name, start, end)),
+            _ => Ok(Function::from(func.name)),
+        }
+    }
+
+    /// Returns the commit of a header line of the logs printed with
+    /// `LOG_FORMAT`, or None for the other lines. The captures are reused
+    /// between the lines.
+    fn header(&self, line: &str, caps: &mut RegexCaptures) -> Option<Commit> {
+        if !self.matcher.captures(line.as_bytes(), caps).ok()? {
+            return None;
+        }
+        let field = |i| caps.get(i).map(|m| line[m].trim_end());
+        Some(Commit {
+            hash: field(1)?.to_owned(),
+            timestamp: field(2).and_then(|t| t.parse().ok()).unwrap_or_default(),
+            author: field(3).unwrap_or_default().to_owned(),
+            subject: field(4).unwrap_or_default().to_owned(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the commits in the output of `git log` and the diffs following
+    /// them. The lines added and removed by the diffs are counted.
+    fn patches(&self, input: &str) -> Result<Vec<Patch>, Error> {
+        let mut patches: Vec<Patch> = vec![];
+        let mut caps = self.matcher.new_captures().map_err(io::Error::from)?;
+        for line in input.lines() {
+            if let Some(commit) = self.header(line, &mut caps) {
+                patches.push(Patch {
+                    commit,
+                    ..Default::default()
+                });
+            } else if let Some(patch) = patches.last_mut() {
+                patch.diff.push_str(line);
+                patch.diff.push('\n');
+            }
+        }
+        for patch in &mut patches {
+            patch.diff = patch.diff.trim_matches('\n').to_owned();
+            patch.hunks = patch::hunks(patch.diff.lines());
+            for (added, removed) in patch.hunks.iter().map(|h| h.churn()) {
+                patch.commit.added += added;
+                patch.commit.removed += removed;
+            }
+        }
+        Ok(patches)
     }
 }
 
+/// Returns the hash algorithm of the repository. Versions of git that can't
+/// tell only support SHA-1.
+fn object_format(git: &Git) -> Result<ObjectFormat, Error> {
+    let output = git.run(["rev-parse", "--show-object-format"])?;
+    match str::from_utf8(&output.stdout)?.trim() {
+        "sha256" => Ok(ObjectFormat::Sha256),
+        _ => Ok(ObjectFormat::Sha1),
+    }
+}
+
+/// Returns the commits at the shallow boundary of the repository. It is empty
+/// when the repository has the complete history.
+fn shallow_boundary(git: &Git) -> Result<Vec<String>, Error> {
+    let output = git.run(["rev-parse", "--git-path", "shallow"])?;
+    let shallow = Path::new(git.path()).join(str::from_utf8(&output.stdout)?.trim());
+    match fs::read_to_string(shallow) {
+        Ok(contents) => Ok(contents.lines().map(str::to_owned).collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns true if the repository was cloned with a filter, which is when it
+/// has a promisor remote.
+fn is_partial(git: &Git) -> Result<bool, Error> {
+    let output = git.run([
+        "config",
+        "--get-regexp",
+        r"^(extensions\.partialclone|remote\..*\.promisor)$",
+    ])?;
+    Ok(str::from_utf8(&output.stdout)?
+        .lines()
+        .filter_map(|l| l.split_once(' '))
+        .any(|(_, value)| value != "false"))
+}
+
 #[cfg(test)]
 mod commits {
     use itertools::assert_equal;
@@ -92,6 +368,10 @@ mod commits {
 
     use super::*;
 
+    fn commits(inspector: &Inspector, input: &str) -> Result<Vec<Commit>, Error> {
+        Ok(inspector.patches(input)?.into_iter().map(|p| p.commit).collect())
+    }
+
     fn new_inspector() -> Inspector {
         let (dir, _) = hotspots_utilities::repo_init();
         let path = dir.path().as_os_str().to_string_lossy().to_string();
@@ -102,7 +382,7 @@ mod commits {
     #[test]
     fn empty_input() -> Result<(), Box<dyn std::error::Error>> {
         let inspector = new_inspector();
-        let res = inspector.commits("")?;
+        let res = commits(&inspector, "")?;
         assert_that!(res).is_empty();
         Ok(())
     }
@@ -111,7 +391,7 @@ mod commits {
     fn no_commit_in_input() -> Result<(), Box<dyn std::error::Error>> {
         let inspector = new_inspector();
         let input = "something\nsomething\ncommit 1234\nnooo";
-        let res = inspector.commits(input)?;
+        let res = commits(&inspector, input)?;
         assert_that!(res).is_empty();
         Ok(())
     }
@@ -121,11 +401,29 @@ mod commits {
         let inspector = new_inspector();
         let hash = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
         let input = format!("commit {hash}");
-        let res = inspector.commits(&input)?;
+        let res = commits(&inspector, &input)?;
         assert_that!(res).has_length(1);
-        assert_that!(res.get(0).unwrap()).is_equal_to(&hash.to_owned());
+        assert_that!(res.first().unwrap().hash).is_equal_to(hash.to_owned());
         Ok(())
     }
+
+    #[test]
+    fn commit_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let inspector = new_inspector();
+        let hash = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let input = format!("commit {hash}\t1700000000\tJane Doe\tFix the\tparser\n");
+        let res = commits(&inspector, &input)?;
+        let want = Commit {
+            hash: hash.to_owned(),
+            timestamp: 1700000000,
+            author: "Jane Doe".to_owned(),
+            subject: "Fix the\tparser".to_owned(),
+            ..Default::default()
+        };
+        assert_that!(res).is_equal_to(vec![want]);
+        Ok(())
+    }
+
     #[test]
     fn finds_commits() -> Result<(), Box<dyn std::error::Error>> {
         let inspector = new_inspector();
@@ -133,8 +431,200 @@ mod commits {
         let hash2 = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
         let hash3 = "cccccccccccccccccccccccccccccccccccccccc";
         let input = format!("commit {hash1}\nnocommit {hash2}\ncommit {hash3}\n");
-        let res = inspector.commits(&input)?;
-        assert_equal(res, vec![hash1, hash3]);
+        let res = commits(&inspector, &input)?;
+        assert_equal(res.iter().map(|c| c.hash.as_str()), vec![hash1, hash3]);
+        Ok(())
+    }
+
+    #[test]
+    fn counts_churn() -> Result<(), Box<dyn std::error::Error>> {
+        let inspector = new_inspector();
+        let hash1 = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let hash2 = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let input = format!(
+            "commit {hash1}\n\ndiff --git lib.rs lib.rs\n--- lib.rs\n+++ lib.rs\n\
+             @@ -1,3 +1,4 @@\n fn a() {{\n-    1\n+    2\n+    3\n }}\n\
+             commit {hash2}\n\ndiff --git lib.rs lib.rs\n--- /dev/null\n+++ lib.rs\n\
+             @@ -0,0 +1,1 @@\n+fn a() {{}}\n"
+        );
+        let res = commits(&inspector, &input)?;
+        let churn = res.iter().map(|c| (c.added, c.removed)).collect::<Vec<_>>();
+        assert_that!(churn).is_equal_to(vec![(2, 1), (1, 0)]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod function_history {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn missing_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (dir, _) = hotspots_utilities::repo_init();
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+        let res = inspector.function_history("nope.rs", "main");
+        match res {
+            Err(Error::History { file, func, stderr }) => {
+                assert_that!(file).is_equal_to("nope.rs".to_owned());
+                assert_that!(func).is_equal_to("main".to_owned());
+                assert_that!(stderr.is_empty()).is_false();
+            },
+            other => panic!("expected a history error, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn patches() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", "fn a() {\n    1\n}\n", "first")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", "fn a() {\n    2\n}\n", "second")?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+
+        let patches = inspector.function_patches("lib.rs", "a")?;
+        let subjects = patches.iter().map(|p| p.commit.subject.as_str()).collect::<Vec<_>>();
+        assert_that!(subjects).is_equal_to(vec!["second", "first"]);
+        assert_that!(patches[0].diff.starts_with("diff --git")).is_true();
+        assert_that!(patches[0].diff.ends_with("-    1\n+    2\n }")).is_true();
+        Ok(())
+    }
+
+    #[test]
+    fn qualified_method() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        let v1 = "class Config:\n    def parse(self):\n        return 1\n";
+        let v2 = "class Config:\n    def parse(self):\n        return 2\n";
+        hotspots_utilities::commit_file(&dir, "config.py", v1, "first")?;
+        hotspots_utilities::commit_file(&dir, "config.py", v2, "second")?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+
+        let res = inspector.function_history("config.py", "Config.parse");
+        assert_that!(res).is_err();
+        let commits = inspector.function_history("config.py", Function::at("Config.parse", 2, 3))?;
+        let subjects = commits.iter().map(|c| c.subject.as_str()).collect::<Vec<_>>();
+        assert_that!(subjects).is_equal_to(vec!["second", "first"]);
+        Ok(())
+    }
+
+    #[test]
+    fn uncommitted_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = hotspots_utilities::cli_repo_init("sha1")?;
+        let v1 = "fn a() {\n    1\n}\n\nfn b() {\n    1\n}\n";
+        let v2 = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        hotspots_utilities::commit_file(&dir, "lib.rs", v1, "first")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", v2, "second")?;
+        let dirty = format!("// one\n// two\n// three\n// four\n{v2}fn c() {{}}\n");
+        std::fs::write(dir.path().join("lib.rs"), dirty)?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+
+        let commits = inspector.function_history("lib.rs", Function::at("a", 5, 7))?;
+        assert_that!(commits).has_length(1);
+        let commits = inspector.function_history("lib.rs", Function::at("b", 9, 11))?;
+        assert_that!(commits).has_length(2);
+        let res = inspector.function_history("lib.rs", Function::at("c", 12, 12));
+        assert_that!(res).is_err();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod clones {
+    use std::process::Command;
+
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    /// Clones a repository with two commits with the given arguments.
+    fn clone(args: &[&str]) -> Result<(tempfile::TempDir, Inspector), Box<dyn std::error::Error>> {
+        let (dir, _) = hotspots_utilities::repo_init();
+        hotspots_utilities::commit_file(&dir, "a.txt", "a", "second")?;
+        hotspots_utilities::git(&dir, &["config", "uploadpack.allowFilter", "true"])?;
+        let target = tempfile::TempDir::new()?;
+        let source = format!("file://{}", dir.path().display());
+        Command::new("git")
+            .args(["clone", "-q"])
+            .args(args)
+            .args([source.as_str(), "."])
+            .current_dir(target.path())
+            .output()?;
+        let inspector = Inspector::new(target.path().to_str().unwrap())?;
+        Ok((target, inspector))
+    }
+
+    #[test]
+    fn complete_history() -> Result<(), Box<dyn std::error::Error>> {
+        let (_dir, inspector) = clone(&[])?;
+        assert_that!(inspector.is_shallow()).is_false();
+        assert_that!(inspector.is_partial()).is_false();
+        Ok(())
+    }
+
+    #[test]
+    fn shallow_clone() -> Result<(), Box<dyn std::error::Error>> {
+        let (_dir, inspector) = clone(&["--depth", "1"])?;
+        assert_that!(inspector.is_shallow()).is_true();
+        let boundary = inspector
+            .shallow
+            .iter()
+            .map(|hash| Commit {
+                hash: hash.clone(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        assert_that!(inspector.truncated("a.txt", &boundary)).is_true();
+        assert_that!(inspector.truncated("a.txt", &[])).is_false();
+        Ok(())
+    }
+
+    #[test]
+    fn partial_clone() -> Result<(), Box<dyn std::error::Error>> {
+        let (_dir, inspector) = clone(&["--filter", "blob:none"])?;
+        assert_that!(inspector.is_partial()).is_true();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod object_formats {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const V1: &str = "fn answer() -> u8 {\n    41\n}\n";
+    const V2: &str = "fn answer() -> u8 {\n    42\n}\n";
+
+    type DynError = Box<dyn std::error::Error>;
+
+    fn history(object_format: &str) -> Result<(Inspector, Vec<Commit>), DynError> {
+        let dir = hotspots_utilities::cli_repo_init(object_format)?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", V1, "first")?;
+        hotspots_utilities::commit_file(&dir, "lib.rs", V2, "second")?;
+        let inspector = Inspector::new(dir.path().to_str().unwrap())?;
+        let commits = inspector.function_history("lib.rs", "answer")?;
+        Ok((inspector, commits))
+    }
+
+    #[test]
+    fn sha1() -> Result<(), DynError> {
+        let (inspector, commits) = history("sha1")?;
+        assert_that!(inspector.object_format()).is_equal_to(ObjectFormat::Sha1);
+        assert_that!(commits).has_length(2);
+        assert!(commits.iter().all(|c| c.hash.len() == 40));
+        assert_that!(commits[0].subject).is_equal_to("second".to_owned());
+        assert_that!(commits[0].author).is_equal_to("name".to_owned());
+        assert_that!(commits[0].timestamp).is_greater_than(0);
+        Ok(())
+    }
+
+    #[test]
+    fn sha256() -> Result<(), DynError> {
+        let (inspector, commits) = history("sha256")?;
+        assert_that!(inspector.object_format()).is_equal_to(ObjectFormat::Sha256);
+        assert_that!(commits).has_length(2);
+        assert!(commits.iter().all(|c| c.hash.len() == 64));
         Ok(())
     }
 }
@@ -0,0 +1,188 @@
+//! This module parses the diffs git prints for the history of a function, and
+//! follows lines of a function back through them.
+use super::Commit;
+
+/// Patch is a commit together with the part of its diff that changed a
+/// function.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Patch {
+    /// The commit that changed the function.
+    pub commit: Commit,
+    /// The diff of the function, as printed by git.
+    pub diff: String,
+    pub(crate) hunks: Vec<Hunk>,
+}
+
+/// Hunk is a range of lines that differ between two versions of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Hunk {
+    /// First line of the hunk in the old version.
+    pub old_start: usize,
+    /// First line of the hunk in the new version.
+    pub new_start: usize,
+    /// Lines of the hunk, prefixed by a space, a + or a -.
+    pub lines: Vec<String>,
+}
+
+/// Origin is where a line of the new version of a file comes from in the old
+/// version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Origin {
+    /// The line is the same as the given line of the old version.
+    Unchanged(usize),
+    /// The line replaced the given line of the old version.
+    Changed(usize),
+    /// The line didn't exist in the old version.
+    Added,
+}
+
+impl Hunk {
+    /// Returns a hunk for a header like `@@ -1,3 +1,4 @@`.
+    fn parse(header: &str) -> Option<Self> {
+        let mut ranges = header.strip_prefix("@@ -")?.split(' ');
+        let old_start = start(ranges.next()?)?;
+        let new_start = start(ranges.next()?.strip_prefix('+')?)?;
+        Some(Hunk {
+            old_start,
+            new_start,
+            lines: vec![],
+        })
+    }
+
+    /// Returns the lines of the new version with their numbers.
+    pub fn new_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines
+            .iter()
+            .filter(|l| !l.starts_with('-'))
+            .enumerate()
+            .map(|(i, l)| (self.new_start + i, &l[1..]))
+    }
+
+    /// Returns the number of added and removed lines.
+    pub fn churn(&self) -> (usize, usize) {
+        let count = |prefix| self.lines.iter().filter(|l| l.starts_with(prefix)).count();
+        (count('+'), count('-'))
+    }
+}
+
+/// Returns the first line of a range like `5,3`. Empty ranges start after the
+/// given line.
+fn start(range: &str) -> Option<usize> {
+    let (start, len) = range.split_once(',').unwrap_or((range, "1"));
+    let start: usize = start.parse().ok()?;
+    match len {
+        "0" => Some(start + 1),
+        _ => Some(start),
+    }
+}
+
+/// Returns the hunks of the diffs in the lines.
+pub(crate) fn hunks<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = vec![];
+    let mut in_hunk = false;
+    for line in lines {
+        if line.starts_with("diff ") {
+            in_hunk = false;
+        } else if let Some(hunk) = Hunk::parse(line) {
+            hunks.push(hunk);
+            in_hunk = true;
+        } else if in_hunk && line.starts_with([' ', '+', '-']) {
+            if let Some(hunk) = hunks.last_mut() {
+                hunk.lines.push(line.to_owned());
+            }
+        }
+    }
+    hunks
+}
+
+/// Returns where the line of the new version comes from in the old version.
+/// Lines replaced in a block of changes are paired with the removed lines in
+/// order.
+pub(crate) fn origin(hunks: &[Hunk], line: usize) -> Origin {
+    let mut offset = 0;
+    for hunk in hunks {
+        if line < hunk.new_start {
+            break;
+        }
+        let (mut old, mut new) = (hunk.old_start, hunk.new_start);
+        let (mut removed_from, mut removed, mut added) = (0, 0, 0);
+        for l in &hunk.lines {
+            if l.starts_with('-') {
+                if removed == 0 || added > 0 {
+                    (removed_from, removed, added) = (old, 0, 0);
+                }
+                removed += 1;
+                old += 1;
+            } else if l.starts_with('+') {
+                if new == line {
+                    if added < removed {
+                        return Origin::Changed(removed_from + added);
+                    }
+                    return Origin::Added;
+                }
+                added += 1;
+                new += 1;
+            } else {
+                if new == line {
+                    return Origin::Unchanged(old);
+                }
+                (removed, added) = (0, 0);
+                old += 1;
+                new += 1;
+            }
+        }
+        offset = old as isize - new as isize;
+    }
+    Origin::Unchanged((line as isize + offset) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const DIFF: &str = "diff --git a/lib.rs b/lib.rs
+--- a/lib.rs
++++ b/lib.rs
+@@ -2,4 +2,5 @@
+ fn a() {
+-    1
++    2
++    3
+ }
+@@ -10,0 +12,1 @@
++fn b() {}
+";
+
+    #[test]
+    fn parses_hunks() {
+        let hunks = hunks(DIFF.lines());
+        assert_that!(hunks).has_length(2);
+        assert_that!(hunks[0].old_start).is_equal_to(2);
+        assert_that!(hunks[0].lines).has_length(5);
+        assert_that!(hunks[0].churn()).is_equal_to((2, 1));
+        assert_that!(hunks[1].old_start).is_equal_to(11);
+        assert_that!(hunks[1].new_start).is_equal_to(12);
+        let lines = hunks[0].new_lines().collect::<Vec<_>>();
+        assert_that!(lines).is_equal_to(vec![
+            (2, "fn a() {"),
+            (3, "    2"),
+            (4, "    3"),
+            (5, "}"),
+        ]);
+    }
+
+    #[test]
+    fn follows_lines() {
+        let hunks = hunks(DIFF.lines());
+        assert_that!(origin(&hunks, 1)).is_equal_to(Origin::Unchanged(1));
+        assert_that!(origin(&hunks, 2)).is_equal_to(Origin::Unchanged(2));
+        assert_that!(origin(&hunks, 3)).is_equal_to(Origin::Changed(3));
+        assert_that!(origin(&hunks, 4)).is_equal_to(Origin::Added);
+        assert_that!(origin(&hunks, 5)).is_equal_to(Origin::Unchanged(4));
+        assert_that!(origin(&hunks, 8)).is_equal_to(Origin::Unchanged(7));
+        assert_that!(origin(&hunks, 12)).is_equal_to(Origin::Added);
+        assert_that!(origin(&hunks, 13)).is_equal_to(Origin::Unchanged(11));
+    }
+}
@@ -0,0 +1,172 @@
+//! This module finds the submodules of a repository, so the history of files
+//! inside them is queried in the repository they belong to.
+use std::path::{Path, PathBuf};
+use std::str;
+
+use super::{Error, Inspector};
+
+/// Submodule is an initialised submodule of the repository.
+pub struct Submodule {
+    /// Path of the submodule relative to the root of the superproject.
+    pub name: String,
+    /// Path of the submodule as seen by the caller.
+    pub path: PathBuf,
+    inspector: Inspector,
+}
+
+impl Inspector {
+    /// Returns the paths of the initialised submodules, including the nested
+    /// ones, joined with the path of the repository.
+    pub fn submodule_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        Ok(self
+            .submodule_names()?
+            .into_iter()
+            .map(|name| Path::new(self.git.path()).join(name))
+            .collect())
+    }
+
+    /// Loads all initialised submodules, so the history of the files inside
+    /// them is queried in their own repository.
+    pub fn with_submodules(mut self) -> Result<Self, Error> {
+        let mut submodules = self
+            .submodule_names()?
+            .into_iter()
+            .map(|name| {
+                let path = Path::new(self.git.path()).join(&name);
+                let git = self.git.at(path.to_str().unwrap_or_default());
+                Ok(Submodule {
+                    name,
+                    path,
+                    inspector: Inspector::with_git(git)?,
+                })
+            })
+            .collect::<Result<Vec<Submodule>, Error>>()?;
+        // Nested submodules come first, so the deepest match wins.
+        submodules.sort_by_key(|s| std::cmp::Reverse(s.path.components().count()));
+        self.submodules = submodules;
+        Ok(self)
+    }
+
+    /// Returns the name of the submodule the file belongs to, or None if it
+    /// belongs to the superproject or submodules are not loaded.
+    pub fn submodule_of(&self, filename: &str) -> Option<&str> {
+        self.submodule(filename).map(|(s, _)| s.name.as_str())
+    }
+
+    /// Returns the submodule the file belongs to, and the path of the file
+    /// relative to the submodule.
+    pub(crate) fn submodule(&self, filename: &str) -> Option<(&Submodule, String)> {
+        self.submodules.iter().find_map(|s| {
+            let rel = Path::new(filename).strip_prefix(&s.path).ok()?;
+            Some((s, rel.to_str()?.to_owned()))
+        })
+    }
+
+    /// Returns the paths of initialised submodules relative to the root of
+    /// the repository.
+    fn submodule_names(&self) -> Result<Vec<String>, Error> {
+        let output = self.git.run(["submodule", "status", "--recursive"])?;
+        Ok(str::from_utf8(&output.stdout)?
+            .lines()
+            .filter(|l| !l.starts_with('-'))
+            .filter_map(|l| l.get(1..)?.split_once(' '))
+            .map(|(_, rest)| match rest.rsplit_once(" (") {
+                Some((path, _)) if rest.ends_with(')') => path.to_owned(),
+                _ => rest.to_owned(),
+            })
+            .collect())
+    }
+}
+
+impl Submodule {
+    /// Returns the Inspector of the submodule.
+    pub fn inspector(&self) -> &Inspector {
+        &self.inspector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    type DynError = Box<dyn std::error::Error>;
+
+    const V1: &str = "fn answer() -> u8 {\n    41\n}\n";
+    const V2: &str = "fn answer() -> u8 {\n    42\n}\n";
+
+    /// Creates a superproject with a submodule named sub, which has two
+    /// commits changing the answer function.
+    fn superproject() -> Result<(TempDir, TempDir), DynError> {
+        let sub = hotspots_utilities::cli_repo_init("sha1")?;
+        hotspots_utilities::commit_file(&sub, "lib.rs", V1, "first")?;
+        hotspots_utilities::commit_file(&sub, "lib.rs", V2, "second")?;
+
+        let root = hotspots_utilities::cli_repo_init("sha1")?;
+        hotspots_utilities::commit_file(&root, "main.rs", V1, "first")?;
+        let source = sub.path().to_str().unwrap();
+        hotspots_utilities::git(&root, &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            source,
+            "sub",
+        ])?;
+        hotspots_utilities::git(&root, &["commit", "-qm", "add submodule"])?;
+        Ok((root, sub))
+    }
+
+    #[test]
+    fn lists_submodules() -> Result<(), DynError> {
+        let (root, _sub) = superproject()?;
+        let inspector = Inspector::new(root.path().to_str().unwrap())?;
+        let paths = inspector.submodule_paths()?;
+        assert_that!(paths).is_equal_to(vec![root.path().join("sub")]);
+        Ok(())
+    }
+
+    #[test]
+    fn not_loaded() -> Result<(), DynError> {
+        let (root, _sub) = superproject()?;
+        let inspector = Inspector::new(root.path().to_str().unwrap())?;
+        let file = root.path().join("sub").join("lib.rs");
+        assert_that!(inspector.submodule_of(file.to_str().unwrap())).is_none();
+        Ok(())
+    }
+
+    #[test]
+    fn history_in_submodule() -> Result<(), DynError> {
+        let (root, _sub) = superproject()?;
+        let inspector = Inspector::new(root.path().to_str().unwrap())?.with_submodules()?;
+        let file = root.path().join("sub").join("lib.rs");
+        let file = file.to_str().unwrap();
+        assert_that!(inspector.submodule_of(file)).is_equal_to(Some("sub"));
+        assert_that!(inspector.function_history(file, "answer")?).has_length(2);
+
+        let file = root.path().join("main.rs");
+        let file = file.to_str().unwrap();
+        assert_that!(inspector.submodule_of(file)).is_none();
+        assert_that!(inspector.function_history(file, "answer")?).has_length(1);
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_in_own_repository() -> Result<(), DynError> {
+        let (root, _sub) = superproject()?;
+        let mut inspector = Inspector::new(root.path().to_str().unwrap())?.with_submodules()?;
+        let file = root.path().join("sub").join("lib.rs");
+        let file = file.to_str().unwrap();
+        let commits = inspector.function_history(file, "answer")?;
+        // The superproject is shallow at the commits of the submodule.
+        inspector.shallow = commits.iter().map(|c| c.hash.clone()).collect();
+        assert_that!(inspector.truncated(file, &commits)).is_false();
+
+        let file = root.path().join("main.rs");
+        assert_that!(inspector.truncated(file.to_str().unwrap(), &commits)).is_true();
+        Ok(())
+    }
+}
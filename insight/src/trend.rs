@@ -0,0 +1,205 @@
+//! This module buckets the history of functions into periods and windows of
+//! time, to see whether a function is heating up or cooling down.
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// Period is the length of each bucket of a trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// Weeks starting on Monday.
+    Week,
+    /// Calendar months.
+    Month,
+    /// Calendar quarters.
+    Quarter,
+}
+
+impl FromStr for Period {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "week" => Ok(Period::Week),
+            "month" => Ok(Period::Month),
+            "quarter" => Ok(Period::Quarter),
+            _ => Err(format!("unknown period: {s}")),
+        }
+    }
+}
+
+/// Bucket holds the number of commits in a period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    /// Start of the period as seconds since the Unix epoch.
+    pub start: i64,
+    /// Number of commits in the period.
+    pub count: usize,
+}
+
+impl Period {
+    /// Returns the first day of the period containing the timestamp.
+    fn start_of(&self, timestamp: i64) -> NaiveDate {
+        let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+            .unwrap_or_default()
+            .date_naive();
+        match self {
+            Period::Week => {
+                date - chrono::Duration::days(date.weekday().num_days_from_monday().into())
+            },
+            Period::Month => date.with_day(1).unwrap_or(date),
+            Period::Quarter => {
+                let month = (date.month0() / 3) * 3 + 1;
+                NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+            },
+        }
+    }
+
+    /// Returns the first day of the period n periods before the given one.
+    fn back(&self, start: NaiveDate, n: u32) -> NaiveDate {
+        match self {
+            Period::Week => start - chrono::Duration::weeks(n.into()),
+            Period::Month => start.checked_sub_months(Months::new(n)).unwrap_or(start),
+            Period::Quarter => start
+                .checked_sub_months(Months::new(n * 3))
+                .unwrap_or(start),
+        }
+    }
+}
+
+/// Returns the number of commits in each of the `count` periods ending with
+/// the period that contains `end`, oldest first. Timestamps before the first
+/// period are ignored.
+pub fn trend(timestamps: &[i64], period: Period, count: usize, end: i64) -> Vec<Bucket> {
+    let last = period.start_of(end);
+    let mut buckets: Vec<Bucket> = (0..count as u32)
+        .rev()
+        .map(|n| Bucket {
+            start: timestamp(period.back(last, n)),
+            count: 0,
+        })
+        .collect();
+    for ts in timestamps {
+        if let Some(bucket) = buckets.iter_mut().rev().find(|b| b.start <= *ts) {
+            bucket.count += 1;
+        }
+    }
+    buckets
+}
+
+/// Window is a span of time, including its start and excluding its end, as
+/// seconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// Start of the window.
+    pub start: i64,
+    /// End of the window.
+    pub end: i64,
+}
+
+impl Window {
+    /// Returns the window of the given days ending at `end`.
+    pub fn ending(end: i64, days: u32) -> Self {
+        Window {
+            start: end - i64::from(days) * DAY,
+            end,
+        }
+    }
+
+    /// Returns the window of the given days ending where this one starts.
+    pub fn before(&self, days: u32) -> Self {
+        Window::ending(self.start, days)
+    }
+
+    /// Returns the number of timestamps in the window.
+    pub fn count(&self, timestamps: &[i64]) -> usize {
+        timestamps
+            .iter()
+            .filter(|ts| (self.start..self.end).contains(ts))
+            .count()
+    }
+
+    /// Returns the number of timestamps in the window per 30 days, so windows
+    /// of different lengths can be compared.
+    pub fn rate(&self, timestamps: &[i64]) -> f64 {
+        let days = (self.end - self.start) as f64 / DAY as f64;
+        if days <= 0.0 {
+            return 0.0;
+        }
+        self.count(timestamps) as f64 * 30.0 / days
+    }
+}
+
+fn timestamp(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .map(|d| d.and_utc().timestamp())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn ts(y: i32, m: u32, d: u32) -> i64 {
+        timestamp(NaiveDate::from_ymd_opt(y, m, d).unwrap())
+    }
+
+    fn counts(buckets: &[Bucket]) -> Vec<usize> {
+        buckets.iter().map(|b| b.count).collect()
+    }
+
+    #[test]
+    fn weekly() {
+        // 2024-01-10 is a Wednesday.
+        let end = ts(2024, 1, 10);
+        let commits = [ts(2024, 1, 8), ts(2024, 1, 7), ts(2024, 1, 1), ts(2023, 12, 1)];
+        let res = trend(&commits, Period::Week, 3, end);
+        assert_that!(res.first().unwrap().start).is_equal_to(ts(2023, 12, 25));
+        assert_that!(counts(&res)).is_equal_to(vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn monthly() {
+        let end = ts(2024, 3, 15);
+        let commits = [ts(2024, 3, 1), ts(2024, 1, 31), ts(2024, 1, 1), ts(2023, 12, 31)];
+        let res = trend(&commits, Period::Month, 3, end);
+        assert_that!(res.first().unwrap().start).is_equal_to(ts(2024, 1, 1));
+        assert_that!(counts(&res)).is_equal_to(vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn quarterly() {
+        let end = ts(2024, 5, 15);
+        let commits = [ts(2024, 4, 1), ts(2024, 3, 31), ts(2023, 10, 1)];
+        let res = trend(&commits, Period::Quarter, 3, end);
+        assert_that!(res.first().unwrap().start).is_equal_to(ts(2023, 10, 1));
+        assert_that!(counts(&res)).is_equal_to(vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn windows() {
+        let end = ts(2024, 4, 1);
+        let recent = Window::ending(end, 30);
+        let reference = recent.before(60);
+        assert_that!(recent.start).is_equal_to(ts(2024, 3, 2));
+        assert_that!(reference.end).is_equal_to(recent.start);
+        assert_that!(reference.start).is_equal_to(ts(2024, 1, 2));
+
+        let commits = [ts(2024, 3, 31), ts(2024, 3, 2), ts(2024, 3, 1), ts(2024, 1, 2), end];
+        assert_that!(recent.count(&commits)).is_equal_to(2);
+        assert_that!(reference.count(&commits)).is_equal_to(2);
+        assert_that!(recent.rate(&commits)).is_equal_to(2.0);
+        assert_that!(reference.rate(&commits)).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn parses_periods() {
+        assert_that!("week".parse::<Period>()).is_equal_to(Ok(Period::Week));
+        assert_that!("quarter".parse::<Period>()).is_equal_to(Ok(Period::Quarter));
+        assert_that!("year".parse::<Period>()).is_err();
+    }
+}
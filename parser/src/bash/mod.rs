@@ -0,0 +1,61 @@
+//! This module implements the parser for Bash and POSIX shell scripts.
+use hotspots_discovery::{File, Lang};
+use hotspots_grammars::bash as language;
+use tree_sitter::{Language, Query};
+
+use super::Error;
+
+/// This parser can parse any shell scripts.
+pub struct BashParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl BashParser {
+    /// Creates a new Bash parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/bash.scm")
+            .ok_or(Error::FileNotFound("bash.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(BashParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for BashParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Bash
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+//! This module implements the parser for C language.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+use tree_sitter_c::language;
+
+use super::Error;
+
+/// This parser can parse any C files, including headers.
+pub struct CParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl CParser {
+    /// Creates a new C parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/c.scm")
+            .ok_or(Error::FileNotFound("c.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(CParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for CParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::C
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    fn definitions(&self) -> &[&str] {
+        &["function_definition"]
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+//! This module implements the parser for C++ language.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+use tree_sitter_cpp::language;
+
+use super::Error;
+
+/// This parser can parse any C++ files, including headers.
+pub struct CppParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl CppParser {
+    /// Creates a new C++ parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/cpp.scm")
+            .ok_or(Error::FileNotFound("cpp.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(CppParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for CppParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Cpp
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Functions are qualified by their namespaces and classes, like
+    /// `app::Config::parse`.
+    fn scopes(&self) -> &[&str] {
+        &["namespace_definition", "class_specifier", "struct_specifier"]
+    }
+
+    fn separator(&self) -> &str {
+        "::"
+    }
+
+    fn definitions(&self) -> &[&str] {
+        &["function_definition"]
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+//! This module implements a parser for languages defined at runtime, with a
+//! tree-sitter grammar loaded from a shared library and a query file.
+use std::fs;
+use std::path::PathBuf;
+
+use hotspots_discovery::{File, Lang};
+use libloading::{Library, Symbol};
+use serde::Deserialize;
+use tree_sitter::{Language, Query};
+
+use super::Error;
+
+/// Definition describes a language that is not built into the crate.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Definition {
+    /// Name of the language, used in the reports.
+    pub name: String,
+    /// Extensions of the files in the language, without the dot.
+    pub extensions: Vec<String>,
+    /// Path of the shared library of the tree-sitter grammar.
+    pub grammar: PathBuf,
+    /// Path of the file with the query that captures the function names as
+    /// `@func_name`.
+    pub query: PathBuf,
+    /// Name of the function in the library that returns the language.
+    /// Defaults to `tree_sitter_<name>`.
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// This parser can parse the files of a language given by a definition.
+pub struct CustomParser {
+    name: String,
+    container: super::Container,
+    language: Language,
+    query: Query,
+    // The library is dropped last, as the language points into it.
+    _library: Library,
+}
+
+impl CustomParser {
+    /// Creates a new parser by loading the grammar and the query of the
+    /// definition. Loading the grammar runs the code of its library, which
+    /// must be trusted. The container should have enough capacity or capable of
+    /// growing to hold all the elements in the file.
+    pub fn new(c: super::Container, def: &Definition) -> Result<Self, Error> {
+        let symbol = def
+            .symbol
+            .clone()
+            .unwrap_or_else(|| format!("tree_sitter_{}", def.name.replace('-', "_")));
+        // SAFETY: the library runs with our rights, so definitions are only
+        // taken from the user, never from the inspected repository. The symbol
+        // is the tree-sitter entry point returning a pointer to the language.
+        let (library, language) = unsafe {
+            let library =
+                Library::new(&def.grammar).map_err(|err| Error::Grammar(err.to_string()))?;
+            let func: Symbol<unsafe extern "C" fn() -> Language> = library
+                .get(symbol.as_bytes())
+                .map_err(|err| Error::Grammar(err.to_string()))?;
+            let language = func();
+            (library, language)
+        };
+        let query = fs::read_to_string(&def.query)
+            .map_err(|err| Error::FileNotFound(format!("{}: {err}", def.query.display())))?;
+        let query = crate::compile_query(language, "", &query, &def.query)?;
+
+        Ok(CustomParser {
+            name: def.name.clone(),
+            container: c,
+            language,
+            query,
+            _library: library,
+        })
+    }
+
+    /// Returns the name of the language.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl super::Parser for CustomParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        matches!(&f.lang, Lang::Custom(name) if *name == self.name)
+    }
+
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+}
+
+#[cfg(test)]
+mod tests;
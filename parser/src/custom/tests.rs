@@ -0,0 +1,75 @@
+use std::error;
+
+use hotspots_discovery::{File, Lang};
+use indicatif::ProgressBar as pb;
+use speculoos::prelude::*;
+
+use super::{CustomParser, Definition};
+use crate::{Container, Error, Parser};
+
+type DynError = Box<dyn error::Error>;
+
+#[test]
+fn no_grammar_file() -> Result<(), DynError> {
+    let def = Definition {
+        name: "dsl".to_owned(),
+        extensions: vec!["dsl".to_owned()],
+        grammar: "src/fixtures/custom/libtree-sitter-dsl.so".into(),
+        query: "src/fixtures/custom/dsl.scm".into(),
+        symbol: None,
+    };
+    let res = CustomParser::new(Container::new(100), &def);
+    assert_that!(matches!(res, Err(Error::Grammar(_)))).is_true();
+    Ok(())
+}
+
+/// Compiles the grammar of the fixtures into a shared library in the
+/// directory, with the C compiler the built-in grammars are compiled with.
+#[cfg(unix)]
+fn grammar(dir: &std::path::Path) -> Result<std::path::PathBuf, DynError> {
+    let path = dir.join("libtree-sitter-dsl.so");
+    let status = std::process::Command::new("cc")
+        .args(["-shared", "-fPIC", "-I", "src/fixtures/custom/src", "-o"])
+        .arg(&path)
+        .arg("src/fixtures/custom/src/parser.c")
+        .status()?;
+    assert_that!(status.success()).is_true();
+    Ok(path)
+}
+
+#[test]
+#[cfg(unix)]
+fn loads_grammar() -> Result<(), DynError> {
+    let dir = tempfile::TempDir::new()?;
+    let def = Definition {
+        name: "dsl".to_owned(),
+        extensions: vec!["dsl".to_owned()],
+        grammar: grammar(dir.path())?,
+        query: "src/fixtures/custom/dsl.scm".into(),
+        symbol: None,
+    };
+    let mut p = CustomParser::new(Container::new(100), &def)?;
+    assert_that!(p.name()).is_equal_to("dsl");
+    p.add_file(File {
+        path: "src/fixtures/custom/functions.dsl".to_owned(),
+        lang: Lang::Custom("dsl".to_owned()),
+    })?;
+    let functions: Vec<(String, usize)> = p
+        .find_functions(&pb::hidden())?
+        .into_iter()
+        .map(|e| (e.name, e.line))
+        .collect();
+    assert_that!(functions).is_equal_to(vec![
+        ("alpha".to_owned(), 1),
+        ("beta".to_owned(), 3),
+        ("gamma".to_owned(), 4),
+    ]);
+
+    let def = Definition {
+        symbol: Some("tree_sitter_missing".to_owned()),
+        ..def
+    };
+    let res = CustomParser::new(Container::new(100), &def);
+    assert_that!(matches!(res, Err(Error::Grammar(_)))).is_true();
+    Ok(())
+}
@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+func_five() { echo five; }
+
+function func_six {
+  local x=1
+  nested() {
+    echo "$x"
+  }
+}
+
+function func_seven() (
+  cd /tmp || exit
+)
+
+func_five
@@ -0,0 +1,6 @@
+#!/bin/sh
+set -e
+
+func_one() {
+  echo one
+}
@@ -0,0 +1,8 @@
+#ifndef POINT_H
+#define POINT_H
+
+static inline int max(int a, int b) {
+    return a > b ? a : b;
+}
+
+#endif
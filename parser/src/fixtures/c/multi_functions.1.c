@@ -0,0 +1,6 @@
+static int func_five(int a) { return a; }
+char *func_six(void) { return 0; }
+void func_seven(void) {
+}
+
+int main(void) { return func_five(1); }
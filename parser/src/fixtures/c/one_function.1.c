@@ -0,0 +1,5 @@
+#include <stdio.h>
+
+int func_one(void) {
+    return 1;
+}
@@ -0,0 +1,22 @@
+namespace app {
+
+class Config {
+public:
+    Config() {}
+    ~Config() {}
+    int method_one() const { return 1; }
+    void method_two();
+
+    struct Inner {
+        void method_three() {}
+    };
+};
+
+void Config::method_two() {}
+
+template <typename T>
+T Box<T>::get() const { return value; }
+
+}  // namespace app
+
+bool operator==(const Point &a, const Point &b) { return true; }
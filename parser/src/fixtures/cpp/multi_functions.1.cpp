@@ -0,0 +1,6 @@
+int func_five() { return 5; }
+template <typename T>
+T func_six(T a) { return a; }
+void func_seven() {
+}
+std::string &func_eight(std::string &s) { return s; }
@@ -0,0 +1,5 @@
+namespace app {
+struct Point {
+    int x;
+};
+}  // namespace app
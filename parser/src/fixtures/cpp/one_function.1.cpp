@@ -0,0 +1,5 @@
+#include <iostream>
+
+int func_one() {
+    return 1;
+}
@@ -0,0 +1 @@
+(function (identifier) @func_name)
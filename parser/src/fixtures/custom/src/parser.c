@@ -0,0 +1,213 @@
+/*
+ * Parser of a tiny language for testing the loading of custom grammars, laid
+ * out like the parsers generated by tree-sitter with ABI 14. It is written by
+ * hand for the grammar:
+ *
+ *   source_file: $ => repeat($.function),
+ *   function: $ => seq('fn', $.identifier),
+ *   identifier: $ => /[a-z_][a-z0-9_]{0,}/,
+ */
+
+#include "tree_sitter/parser.h"
+
+#define LANGUAGE_VERSION 14
+#define STATE_COUNT 8
+#define LARGE_STATE_COUNT 8
+#define SYMBOL_COUNT 6
+#define ALIAS_COUNT 0
+#define TOKEN_COUNT 3
+#define EXTERNAL_TOKEN_COUNT 0
+#define FIELD_COUNT 0
+#define MAX_ALIAS_SEQUENCE_LENGTH 2
+#define PRODUCTION_ID_COUNT 1
+
+enum ts_symbol_identifiers {
+  anon_sym_fn = 1,
+  sym_identifier = 2,
+  sym_source_file = 3,
+  sym_function = 4,
+  aux_sym_source_file_repeat1 = 5,
+};
+
+static const char * const ts_symbol_names[] = {
+  [ts_builtin_sym_end] = "end",
+  [anon_sym_fn] = "fn",
+  [sym_identifier] = "identifier",
+  [sym_source_file] = "source_file",
+  [sym_function] = "function",
+  [aux_sym_source_file_repeat1] = "source_file_repeat1",
+};
+
+static const TSSymbol ts_symbol_map[] = {
+  [ts_builtin_sym_end] = ts_builtin_sym_end,
+  [anon_sym_fn] = anon_sym_fn,
+  [sym_identifier] = sym_identifier,
+  [sym_source_file] = sym_source_file,
+  [sym_function] = sym_function,
+  [aux_sym_source_file_repeat1] = aux_sym_source_file_repeat1,
+};
+
+static const TSSymbolMetadata ts_symbol_metadata[] = {
+  [ts_builtin_sym_end] = {.visible = false, .named = true},
+  [anon_sym_fn] = {.visible = true, .named = false},
+  [sym_identifier] = {.visible = true, .named = true},
+  [sym_source_file] = {.visible = true, .named = true},
+  [sym_function] = {.visible = true, .named = true},
+  [aux_sym_source_file_repeat1] = {.visible = false, .named = false},
+};
+
+static const TSSymbol ts_alias_sequences[PRODUCTION_ID_COUNT][MAX_ALIAS_SEQUENCE_LENGTH] = {
+  [0] = {0},
+};
+
+static const uint16_t ts_non_terminal_alias_map[] = {
+  0,
+};
+
+static const TSStateId ts_primary_state_ids[STATE_COUNT] = {
+  [0] = 0, [1] = 1, [2] = 2, [3] = 3, [4] = 4, [5] = 5, [6] = 6, [7] = 7,
+};
+
+static bool ts_lex(TSLexer *lexer, TSStateId state) {
+  START_LEXER();
+  eof = lexer->eof(lexer);
+  switch (state) {
+    case 0:
+      if (eof) ADVANCE(5);
+      if (lookahead == 'f') ADVANCE(1);
+      if (lookahead == '\t' || lookahead == '\n' || lookahead == '\r' || lookahead == ' ') SKIP(0);
+      END_STATE();
+    case 1:
+      if (lookahead == 'n') ADVANCE(2);
+      END_STATE();
+    case 2:
+      ACCEPT_TOKEN(anon_sym_fn);
+      END_STATE();
+    case 3:
+      if (eof) ADVANCE(5);
+      if (lookahead == '\t' || lookahead == '\n' || lookahead == '\r' || lookahead == ' ') SKIP(3);
+      if (lookahead == '_' || ('a' <= lookahead && lookahead <= 'z')) ADVANCE(4);
+      END_STATE();
+    case 4:
+      ACCEPT_TOKEN(sym_identifier);
+      if (lookahead == '_' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(4);
+      END_STATE();
+    case 5:
+      ACCEPT_TOKEN(ts_builtin_sym_end);
+      END_STATE();
+    default:
+      return false;
+  }
+}
+
+static const TSLexMode ts_lex_modes[STATE_COUNT] = {
+  [0] = {.lex_state = 0},
+  [1] = {.lex_state = 0},
+  [2] = {.lex_state = 0},
+  [3] = {.lex_state = 0},
+  [4] = {.lex_state = 3},
+  [5] = {.lex_state = 0},
+  [6] = {.lex_state = 0},
+  [7] = {.lex_state = 0},
+};
+
+static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
+  [0] = {
+    [ts_builtin_sym_end] = ACTIONS(1),
+    [anon_sym_fn] = ACTIONS(1),
+    [sym_identifier] = ACTIONS(1),
+  },
+  [1] = {
+    [ts_builtin_sym_end] = ACTIONS(3),
+    [anon_sym_fn] = ACTIONS(5),
+    [sym_source_file] = STATE(2),
+    [sym_function] = STATE(5),
+    [aux_sym_source_file_repeat1] = STATE(3),
+  },
+  [2] = {
+    [ts_builtin_sym_end] = ACTIONS(7),
+  },
+  [3] = {
+    [ts_builtin_sym_end] = ACTIONS(9),
+    [anon_sym_fn] = ACTIONS(5),
+    [sym_function] = STATE(6),
+  },
+  [4] = {
+    [sym_identifier] = ACTIONS(11),
+  },
+  [5] = {
+    [ts_builtin_sym_end] = ACTIONS(13),
+    [anon_sym_fn] = ACTIONS(13),
+  },
+  [6] = {
+    [ts_builtin_sym_end] = ACTIONS(15),
+    [anon_sym_fn] = ACTIONS(15),
+  },
+  [7] = {
+    [ts_builtin_sym_end] = ACTIONS(17),
+    [anon_sym_fn] = ACTIONS(17),
+  },
+};
+
+static const uint16_t ts_small_parse_table[] = {
+  0,
+};
+
+static const uint32_t ts_small_parse_table_map[] = {
+  0,
+};
+
+static const TSParseActionEntry ts_parse_actions[] = {
+  [0] = {.entry = {.count = 0, .reusable = false}},
+  [1] = {.entry = {.count = 1, .reusable = false}}, RECOVER(),
+  [3] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 0, 0, 0),
+  [5] = {.entry = {.count = 1, .reusable = true}}, SHIFT(4),
+  [7] = {.entry = {.count = 1, .reusable = true}}, ACCEPT_INPUT(),
+  [9] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 1, 0, 0),
+  [11] = {.entry = {.count = 1, .reusable = true}}, SHIFT(7),
+  [13] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 1, 0, 0),
+  [15] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2, 0, 0),
+  [17] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_function, 2, 0, 0),
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+#ifdef _WIN32
+#define TS_PUBLIC __declspec(dllexport)
+#else
+#define TS_PUBLIC __attribute__((visibility("default")))
+#endif
+
+TS_PUBLIC const TSLanguage *tree_sitter_dsl(void) {
+  static const TSLanguage language = {
+    .version = LANGUAGE_VERSION,
+    .symbol_count = SYMBOL_COUNT,
+    .alias_count = ALIAS_COUNT,
+    .token_count = TOKEN_COUNT,
+    .external_token_count = EXTERNAL_TOKEN_COUNT,
+    .state_count = STATE_COUNT,
+    .large_state_count = LARGE_STATE_COUNT,
+    .production_id_count = PRODUCTION_ID_COUNT,
+    .field_count = FIELD_COUNT,
+    .max_alias_sequence_length = MAX_ALIAS_SEQUENCE_LENGTH,
+    .parse_table = &ts_parse_table[0][0],
+    .small_parse_table = ts_small_parse_table,
+    .small_parse_table_map = ts_small_parse_table_map,
+    .parse_actions = ts_parse_actions,
+    .symbol_names = ts_symbol_names,
+    .symbol_metadata = ts_symbol_metadata,
+    .public_symbol_map = ts_symbol_map,
+    .alias_map = ts_non_terminal_alias_map,
+    .alias_sequences = &ts_alias_sequences[0][0],
+    .lex_modes = ts_lex_modes,
+    .lex_fn = ts_lex,
+    .primary_state_ids = ts_primary_state_ids,
+  };
+  return &language;
+}
+#ifdef __cplusplus
+}
+#endif
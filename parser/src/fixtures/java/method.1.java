@@ -0,0 +1,32 @@
+public class Config {
+    private final Runnable reload = () -> {};
+
+    public Config() {
+    }
+
+    public Config(String path) {
+    }
+
+    void parse(String input) {
+    }
+
+    void parse(String input, int[] limits) {
+    }
+
+    static void log(String... parts) {
+    }
+
+    static class Builder {
+        Config build() {
+            return new Config();
+        }
+    }
+
+    enum Mode {
+        FAST;
+
+        boolean quick() {
+            return true;
+        }
+    }
+}
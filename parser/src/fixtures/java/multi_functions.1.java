@@ -0,0 +1,9 @@
+interface Shape {
+    double area();
+    default String name() { return "shape"; }
+}
+record Point(int x, int y) {
+    Point {
+    }
+    int sum() { return x + y; }
+}
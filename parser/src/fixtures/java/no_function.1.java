@@ -0,0 +1,5 @@
+package app;
+
+public class Empty {
+    private int count;
+}
@@ -0,0 +1,7 @@
+package app;
+
+public class One {
+    static int funcOne() {
+        return 1;
+    }
+}
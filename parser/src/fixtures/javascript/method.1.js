@@ -0,0 +1,19 @@
+class Config {
+  method_one() {}
+
+  static async method_two() {}
+
+  #method_three() {}
+}
+
+const api = {
+  method_four() {},
+  method_five: () => {},
+  nested: {
+    method_six: function () {},
+  },
+};
+
+class Handler {
+  handle = () => {};
+}
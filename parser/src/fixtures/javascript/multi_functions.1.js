@@ -0,0 +1,5 @@
+function func_five() {}
+const func_six = () => {
+  const nested = function () {};
+};
+export async function* func_seven() {}
@@ -0,0 +1,3 @@
+import fs from "fs";
+
+function func_one() {}
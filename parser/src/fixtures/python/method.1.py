@@ -0,0 +1,14 @@
+class Config:
+    def method_one(self):
+        pass
+
+    async def method_two(self):
+        pass
+
+    @staticmethod
+    def method_three():
+        pass
+
+    class Inner:
+        def method_four(self):
+            pass
@@ -0,0 +1,11 @@
+def func_five():
+    pass
+async def func_six():
+    def nested():
+        pass
+    return nested
+
+
+@decorator
+def func_seven():
+    pass
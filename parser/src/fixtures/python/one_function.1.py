@@ -0,0 +1,5 @@
+import os
+
+
+def func_one():
+    pass
@@ -0,0 +1,2 @@
+; extends
+(struct_item name: (type_identifier) @func_name)
@@ -0,0 +1,3 @@
+; extends
+
+(struct_item nmae: (type_identifier) @func_name)
@@ -0,0 +1,3 @@
+(function_item name: (identifier) @func_name)
+
+(function_item nmae: (identifier) @func_name)
@@ -0,0 +1 @@
+(function_item name: (identifier) @func_name)
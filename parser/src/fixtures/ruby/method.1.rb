@@ -0,0 +1,22 @@
+module Ops
+  class Deploy < Base
+    def method_one
+    end
+
+    def self.method_two
+    end
+
+    class << self
+      def method_three; end
+    end
+  end
+
+  def method_four
+  end
+end
+
+class Ops::Config
+  def method_five(path)
+    path
+  end
+end
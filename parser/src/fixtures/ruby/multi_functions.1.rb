@@ -0,0 +1,7 @@
+def func_five; end
+def func_six(a, b)
+  nested = ->(x) { x }
+  nested.call(a)
+end
+
+def valid? = true
@@ -0,0 +1,3 @@
+require "json"
+
+X = 1
@@ -0,0 +1,5 @@
+require "json"
+
+def func_one
+  JSON.parse("{}")
+end
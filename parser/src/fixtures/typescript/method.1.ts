@@ -0,0 +1,13 @@
+abstract class Config<T> {
+  method_one(): void {}
+
+  private async method_two(a: T): Promise<T> { return a; }
+}
+
+export class Service extends Config<number> {
+  public method_three = () => {};
+}
+
+const api = {
+  method_four(): number { return 1; },
+};
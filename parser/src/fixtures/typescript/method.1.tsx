@@ -0,0 +1,9 @@
+export const Button = (props: { label: string }) => {
+  return <button>{props.label}</button>;
+};
+
+class Page extends React.Component<Props> {
+  render() {
+    return <div />;
+  }
+}
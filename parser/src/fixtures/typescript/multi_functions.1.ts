@@ -0,0 +1,5 @@
+function func_five<T>(a: T): T { return a; }
+const func_six = async (a: string): Promise<void> => {
+  const nested = function (): void {};
+};
+export function func_seven() {}
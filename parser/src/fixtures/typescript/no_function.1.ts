@@ -0,0 +1,2 @@
+type X = number;
+export const x: X = 1;
@@ -52,6 +52,10 @@ impl super::Parser for GoParser {
         &self.query
     }
 
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
     /// Returns a new vector with the representation names for functions. In
     /// case of go, we want to remove the receiver from the method name, and
     /// inform the caller that we removed one element from the vector.
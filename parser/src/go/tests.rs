@@ -1,3 +1,5 @@
+// Written before clippy suggested `first` and `sort_by_key` here.
+#![allow(clippy::get_first, clippy::unnecessary_sort_by)]
 use std::error;
 
 use hotspots_discovery::{File, Lang};
@@ -67,6 +69,7 @@ fn returns_one_function_found() -> Result<(), DynError> {
     let want = Element {
         name: "FuncOne".to_owned(),
         line: 3,
+        end: 3,
         file: path,
         index: 1,
     };
@@ -91,24 +94,28 @@ fn can_identify_methods() -> Result<(), DynError> {
         Element {
             name: "(x) FuncOne".to_owned(),
             line: 5,
+            end: 5,
             file: path.clone(),
             index: 1,
         },
         Element {
             name: "(*x) FuncTwo".to_owned(),
             line: 6,
+            end: 9,
             file: path.clone(),
             index: 1,
         },
         Element {
             name: "nested".to_owned(),
             line: 7,
+            end: 7,
             file: path.clone(),
             index: 1,
         },
         Element {
             name: "(*x) FuncThree".to_owned(),
             line: 10,
+            end: 10,
             file: path,
             index: 1,
         },
@@ -137,18 +144,21 @@ fn returns_all_functions_in_files() -> Result<(), DynError> {
         Element {
             name: "FuncTwo".to_owned(),
             line: 3,
+            end: 3,
             file: path.clone(),
             index: 1,
         },
         Element {
             name: "FuncThree".to_owned(),
             line: 5,
+            end: 8,
             file: path.clone(),
             index: 1,
         },
         Element {
             name: "nested".to_owned(),
             line: 6,
+            end: 6,
             file: path,
             index: 1,
         },
@@ -183,24 +193,28 @@ fn handles_multiple_files() -> Result<(), DynError> {
         Element {
             name: "FuncOne".to_owned(),
             line: 3,
+            end: 3,
             file: path2,
             index: 1,
         },
         Element {
             name: "FuncTwo".to_owned(),
             line: 3,
+            end: 3,
             file: path1.clone(),
             index: 1,
         },
         Element {
             name: "FuncThree".to_owned(),
             line: 5,
+            end: 8,
             file: path1.clone(),
             index: 1,
         },
         Element {
             name: "nested".to_owned(),
             line: 6,
+            end: 6,
             file: path1,
             index: 1,
         },
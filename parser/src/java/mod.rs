@@ -0,0 +1,98 @@
+//! This module implements the parser for Java language.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Node, Query};
+use tree_sitter_java::language;
+
+use super::Error;
+
+/// This parser can parse any Java files.
+pub struct JavaParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl JavaParser {
+    /// Creates a new Java parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/java.scm")
+            .ok_or(Error::FileNotFound("java.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(JavaParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for JavaParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Java
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their outer and inner classes, like
+    /// `Config.Builder.build`.
+    fn scopes(&self) -> &[&str] {
+        &[
+            "class_declaration",
+            "interface_declaration",
+            "enum_declaration",
+            "record_declaration",
+        ]
+    }
+
+    /// Overloaded methods and constructors are told apart by the types of
+    /// their parameters, like `Config.parse(String, int)`.
+    fn signature(&self, name: Node, source: &str) -> Option<String> {
+        let params = name.parent()?.child_by_field_name("parameters")?;
+        let text = |n: Node| n.utf8_text(source.as_bytes()).unwrap_or_default().to_owned();
+        let mut cursor = params.walk();
+        let types: Vec<String> = params
+            .named_children(&mut cursor)
+            .filter_map(|param| match param.kind() {
+                "formal_parameter" => {
+                    let dimensions = param.child_by_field_name("dimensions").map(text);
+                    Some(text(param.child_by_field_name("type")?) + &dimensions.unwrap_or_default())
+                },
+                "spread_parameter" => {
+                    let mut cursor = param.walk();
+                    let kind = param.named_children(&mut cursor).find(|n| {
+                        !matches!(n.kind(), "modifiers" | "variable_declarator")
+                    })?;
+                    Some(text(kind) + "...")
+                },
+                _ => None,
+            })
+            .collect();
+        Some(format!("({})", types.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests;
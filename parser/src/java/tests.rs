@@ -0,0 +1,274 @@
+use std::error;
+
+use hotspots_discovery::{File, Lang};
+use indicatif::ProgressBar as pb;
+use itertools::assert_equal;
+use speculoos::prelude::*;
+
+use super::JavaParser;
+use crate::{Container, Element, Parser};
+
+const FIXTURES: &str = "src/fixtures/java";
+
+type DynError = Box<dyn error::Error>;
+
+#[test]
+fn no_file_added() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_err();
+    Ok(())
+}
+
+#[test]
+fn no_java_file() -> Result<(), DynError> {
+    let some_types = vec![
+        Lang::Undefined,
+        Lang::Rust,
+        Lang::Go,
+        Lang::Lua,
+        Lang::Python,
+        Lang::C,
+    ];
+    for t in some_types {
+        let mut p = JavaParser::new(Container::new(100))?;
+        let f = File {
+            path: format!("{FIXTURES}/no_file.1.java"),
+            lang: t,
+        };
+        let res = p.add_file(f);
+        assert_that!(res).is_err();
+    }
+    Ok(())
+}
+
+#[test]
+fn no_function_in_file() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let f = File {
+        path: format!("{FIXTURES}/no_function.1.java"),
+        lang: Lang::Java,
+    };
+    p.add_file(f)?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_ok();
+    assert_that!(res.unwrap()).is_empty();
+    Ok(())
+}
+
+#[test]
+fn returns_one_function_found() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let path = format!("{FIXTURES}/one_function.1.java");
+    let f = File {
+        path: path.clone(),
+        lang: Lang::Java,
+    };
+    p.add_file(f)?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_ok();
+
+    let res = res.unwrap();
+    assert_that!(res).has_length(1);
+    let element = res.first().unwrap();
+    let want = Element {
+        name: "One.funcOne".to_owned(),
+        line: 4,
+        end: 6,
+        file: path,
+        index: 0,
+    };
+    assert_that!(element).is_equal_to(&want);
+    Ok(())
+}
+
+#[test]
+fn can_identify_methods() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let path = format!("{FIXTURES}/method.1.java");
+    let f = File {
+        path: path.clone(),
+        lang: Lang::Java,
+    };
+    p.add_file(f)?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_ok();
+
+    let mut res = res.unwrap();
+    let mut want = vec![
+        Element {
+            name: "Config.reload".to_owned(),
+            line: 2,
+            end: 2,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.Config()".to_owned(),
+            line: 4,
+            end: 5,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.Config(String)".to_owned(),
+            line: 7,
+            end: 8,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.parse(String)".to_owned(),
+            line: 10,
+            end: 11,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.parse(String, int[])".to_owned(),
+            line: 13,
+            end: 14,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.log".to_owned(),
+            line: 16,
+            end: 17,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.Builder.build".to_owned(),
+            line: 20,
+            end: 22,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Config.Mode.quick".to_owned(),
+            line: 28,
+            end: 30,
+            file: path,
+            index: 0,
+        },
+    ];
+    want.sort_by_key(|e| e.line);
+    res.sort_by_key(|e| e.line);
+
+    assert_equal(want, res);
+    Ok(())
+}
+
+#[test]
+fn returns_all_functions_in_files() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let path = format!("{FIXTURES}/multi_functions.1.java");
+    let f = File {
+        path: path.clone(),
+        lang: Lang::Java,
+    };
+    p.add_file(f)?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_ok();
+
+    let mut res = res.unwrap();
+    let mut want = vec![
+        Element {
+            name: "Shape.area".to_owned(),
+            line: 2,
+            end: 2,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Shape.name".to_owned(),
+            line: 3,
+            end: 3,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Point.Point".to_owned(),
+            line: 6,
+            end: 7,
+            file: path.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Point.sum".to_owned(),
+            line: 8,
+            end: 8,
+            file: path,
+            index: 0,
+        },
+    ];
+    want.sort_by(|a, b| a.name.cmp(&b.name));
+    res.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_equal(want, res);
+    Ok(())
+}
+
+#[test]
+fn handles_multiple_files() -> Result<(), DynError> {
+    let mut p = JavaParser::new(Container::new(100))?;
+    let path1 = format!("{FIXTURES}/multi_functions.1.java");
+    let path2 = format!("{FIXTURES}/one_function.1.java");
+    let f1 = File {
+        path: path1.clone(),
+        lang: Lang::Java,
+    };
+    let f2 = File {
+        path: path2.clone(),
+        lang: Lang::Java,
+    };
+    p.add_file(f1)?;
+    p.add_file(f2)?;
+    let res = p.find_functions(&pb::hidden());
+    assert_that!(res).is_ok();
+
+    let mut res = res.unwrap();
+    let mut want = vec![
+        Element {
+            name: "One.funcOne".to_owned(),
+            line: 4,
+            end: 6,
+            file: path2,
+            index: 0,
+        },
+        Element {
+            name: "Point.Point".to_owned(),
+            line: 6,
+            end: 7,
+            file: path1.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Point.sum".to_owned(),
+            line: 8,
+            end: 8,
+            file: path1.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Shape.area".to_owned(),
+            line: 2,
+            end: 2,
+            file: path1.clone(),
+            index: 0,
+        },
+        Element {
+            name: "Shape.name".to_owned(),
+            line: 3,
+            end: 3,
+            file: path1,
+            index: 0,
+        },
+    ];
+    want.sort_by(|a, b| a.name.cmp(&b.name));
+    res.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_equal(want, res);
+    Ok(())
+}
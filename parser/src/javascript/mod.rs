@@ -0,0 +1,66 @@
+//! This module implements the parser for JavaScript language, including JSX.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+use tree_sitter_javascript::language;
+
+use super::Error;
+
+/// This parser can parse any JavaScript and JSX files.
+pub struct JavaScriptParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl JavaScriptParser {
+    /// Creates a new JavaScript parser. The container should have enough
+    /// capacity or capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/javascript.scm")
+            .ok_or(Error::FileNotFound("javascript.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(JavaScriptParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for JavaScriptParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::JavaScript
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their classes or objects, like `Config.parse`.
+    fn scopes(&self) -> &[&str] {
+        &["class_declaration", "class", "object"]
+    }
+}
+
+#[cfg(test)]
+mod tests;
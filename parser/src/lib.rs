@@ -1,15 +1,41 @@
 //! This crate parses supported languages with their tree-sitter grammers, and
 //! collects all the methods and functions in the files.
 //!
+//! Each language is behind a cargo feature of the same name, all enabled by
+//! default.
+//!
 //! All tree-sitter queries are stored in the `queries` directory.
 //! All fixtures for testing are stored in the `fixtures` directory.
 #![warn(missing_docs)]
+#[cfg(feature = "bash")]
+pub mod bash;
+#[cfg(feature = "c")]
+pub mod c;
+#[cfg(feature = "cpp")]
+pub mod cpp;
+pub mod custom;
+#[cfg(feature = "go")]
 pub mod go;
+#[cfg(feature = "java")]
+pub mod java;
+#[cfg(feature = "javascript")]
+pub mod javascript;
+#[cfg(feature = "lua")]
 pub mod lua;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod registry;
+#[cfg(feature = "ruby")]
+pub mod ruby;
+#[cfg(feature = "rust")]
 pub mod rust;
+#[cfg(feature = "typescript")]
+pub mod typescript;
 
+use std::collections::HashMap;
 use std::io::{BufReader, Read};
 use std::ops::Not;
+use std::path::Path;
 use std::str::Utf8Error;
 use std::time::Instant;
 use std::{fs, io};
@@ -18,8 +44,11 @@ use hotspots_discovery::File;
 use include_dir::{include_dir, Dir};
 use indicatif::ProgressBar;
 use log::{debug, warn};
+use rayon::prelude::*;
 use thiserror::Error as TError;
-use tree_sitter::{Language, LanguageError, Parser as TSParser, Query, QueryCursor, QueryMatch};
+use tree_sitter::{
+    Language, LanguageError, Node, Parser as TSParser, Query, QueryCursor, QueryMatch,
+};
 
 static PROJECT_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR");
 
@@ -54,11 +83,40 @@ pub enum Error {
     #[error(transparent)]
     Utf8Str(#[from] Utf8Error),
 
+    /// Returned when a query file is invalid, with its position in the file.
+    #[error("Invalid query {0}")]
+    QueryFile(String),
+
+    /// Returned when the grammar of a custom language can't be loaded.
+    #[error("Can't load grammar {0}")]
+    Grammar(String),
+
     /// Returned when tree-sitter can't set the given language.
     #[error(transparent)]
     Language(#[from] LanguageError),
 }
 
+/// Returns the query in the file, compiled after the prefix, like an embedded
+/// query the file extends. The errors are reported against the position in the
+/// file.
+pub(crate) fn compile_query(
+    language: Language,
+    prefix: &str,
+    source: &str,
+    path: &Path,
+) -> Result<Query, Error> {
+    Query::new(language, &format!("{prefix}{source}")).map_err(|err| {
+        Error::QueryFile(format!(
+            "{}:{}:{}: {:?} error: {}",
+            path.display(),
+            err.row.saturating_sub(prefix.matches('\n').count()) + 1,
+            err.column + 1,
+            err.kind,
+            err.message,
+        ))
+    })
+}
+
 /// Element represents a function or a method in a file.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Element {
@@ -68,6 +126,9 @@ pub struct Element {
     pub file: String,
     /// Line where the function or method is located.
     pub line: usize,
+    /// Last line of the definition of the function or method. The history is
+    /// followed for the lines from `line` to `end`, as the name is only a label.
+    pub end: usize,
     index: u32,
 }
 
@@ -91,27 +152,154 @@ impl Container {
 fn collect_matches<'a>(
     matches: impl Iterator<Item = QueryMatch<'a, 'a>>,
     source: &'a str,
-) -> Vec<(usize, u32, &'a str)> {
-    matches
+    scopes: &[&str],
+    separator: &str,
+    definitions: &[&str],
+    signature: impl Fn(Node) -> Option<String>,
+) -> Vec<(usize, usize, u32, String)> {
+    let res: Vec<_> = matches
         .filter_map(|m| {
             m.captures.iter().find_map(|capture| {
                 if let Ok(line) = capture.node.utf8_text(source.as_bytes()) {
+                    let start = capture.node.range().start_point.row + 1;
                     Some((
-                        capture.node.range().start_point.row + 1,
+                        start,
+                        last_line(definition(capture.node, definitions)).max(start),
                         capture.index,
-                        line,
+                        qualify(capture.node, line, source, scopes, separator),
+                        signature(capture.node),
                     ))
                 } else {
                     None
                 }
             })
         })
+        .collect();
+    disambiguate(res)
+}
+
+/// Appends the signatures to the names of overloaded functions, so each
+/// overload has a name of its own.
+fn disambiguate(
+    res: Vec<(usize, usize, u32, String, Option<String>)>,
+) -> Vec<(usize, usize, u32, String)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (_, _, _, name, _) in &res {
+        *counts.entry(name.clone()).or_default() += 1;
+    }
+    res.into_iter()
+        .map(|(line, end, index, name, signature)| match signature {
+            Some(signature) if counts[&name] > 1 => (line, end, index, name + &signature),
+            _ => (line, end, index, name),
+        })
         .collect()
 }
 
+/// Returns the node holding the whole definition of the function with the
+/// given name node, which is its closest ancestor of the given kinds, or its
+/// parent when there is none.
+fn definition<'a>(name: Node<'a>, kinds: &[&str]) -> Node<'a> {
+    let mut parent = name.parent();
+    while let Some(p) = parent {
+        if kinds.contains(&p.kind()) {
+            return p;
+        }
+        parent = p.parent();
+    }
+    name.parent().unwrap_or(name)
+}
+
+/// Returns the last line of the node. A node ending at the start of a line
+/// ends on the previous one.
+fn last_line(node: Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row
+    } else {
+        end.row + 1
+    }
+}
+
+/// Returns the name prefixed with the names of the enclosing nodes of the
+/// given kinds, for example `Class.method`.
+fn qualify(node: Node, name: &str, source: &str, scopes: &[&str], separator: &str) -> String {
+    let mut names = vec![name];
+    let mut parent = node.parent();
+    while let Some(p) = parent {
+        if scopes.contains(&p.kind()) {
+            if let Some(scope) = scope_name(p).and_then(|n| n.utf8_text(source.as_bytes()).ok()) {
+                names.push(scope);
+            }
+        }
+        parent = p.parent();
+    }
+    names.reverse();
+    names.join(separator)
+}
+
+/// Returns the name of the scope. Anonymous scopes, like object literals, are
+/// named after the variable or the key they are assigned to.
+fn scope_name(scope: Node) -> Option<Node> {
+    scope.child_by_field_name("name").or_else(|| {
+        let parent = scope.parent()?;
+        parent
+            .child_by_field_name("name")
+            .or_else(|| parent.child_by_field_name("key"))
+    })
+}
+
+/// Returns the functions in the file, parsed with the given tree-sitter parser.
+fn parse_file<P: Parser + ?Sized>(
+    p: &P,
+    parser: &mut TSParser,
+    file: &File,
+    pb: &ProgressBar,
+) -> Result<Vec<Element>, Error> {
+    let file_handle = fs::File::open(&file.path)?;
+    let mut reader = BufReader::new(file_handle);
+    let mut source_code = String::new();
+    if let Err(err) = reader.read_to_string(&mut source_code) {
+        warn!("error while reading {}: {err}", file.path.clone());
+        return Ok(vec![]);
+    };
+    let tree = match parser.parse(&source_code, None) {
+        Some(tree) => tree,
+        None => {
+            warn!("error while parsing {}", file.path.clone());
+            return Ok(vec![]);
+        },
+    };
+
+    let mut cursor = QueryCursor::new();
+
+    let matches = cursor.matches(p.query(), tree.root_node(), source_code.as_bytes());
+    let res = collect_matches(
+        matches,
+        &source_code,
+        p.scopes(),
+        p.separator(),
+        p.definitions(),
+        |node| p.signature(node, &source_code),
+    );
+    Ok(res
+        .into_iter()
+        .map(|(line, end, index, name)| {
+            pb.inc_length(1);
+            Element {
+                name,
+                file: file.path.clone(),
+                line,
+                end,
+                index,
+            }
+        })
+        .collect())
+}
+
 /// Parser provides the functionalities necessary for finding tree-sitter Nodes
-/// from a list of given files.
-pub trait Parser {
+/// from a list of given files. Parsers are shared between the workers parsing
+/// the files.
+pub trait Parser: Sync {
     /// Returns a mutable reference to the container.
     fn container(&mut self) -> &mut Container;
 
@@ -136,6 +324,33 @@ pub trait Parser {
     /// Returns a tree-sitter Query object for the Parser's language.
     fn query(&self) -> &Query;
 
+    /// Replaces the query of the Parser, for example with one given by the
+    /// project.
+    fn set_query(&mut self, query: Query);
+
+    /// Returns the kinds of nodes, like classes, whose names qualify the
+    /// functions inside them.
+    fn scopes(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Returns the separator between the names of scopes and functions.
+    fn separator(&self) -> &str {
+        "."
+    }
+
+    /// Returns the kinds of nodes holding the whole definition of a function,
+    /// when the parent of its name node doesn't.
+    fn definitions(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Returns the signature of the function with the given name node, like
+    /// `(String, int)`. It tells overloaded functions apart.
+    fn signature(&self, _name: Node, _source: &str) -> Option<String> {
+        None
+    }
+
     /// Returns a mutable reference to the given files. It returns and error if
     /// the file can't be read.
     fn files(&self) -> Result<&[File], Error> {
@@ -162,52 +377,36 @@ pub trait Parser {
         (v, 0)
     }
 
-    /// Returns all the functions in all files. It returns and error if the file
+    /// Returns all the functions in all files. Files are parsed in parallel,
+    /// with a tree-sitter parser for each worker, and the functions are
+    /// returned in the order of the files. It returns and error if the file
     /// can't be read, or the language parser can't parse the contents.
     fn find_functions(&mut self, pb: &ProgressBar) -> Result<Vec<Element>, Error> {
-        let mut parser = TSParser::new();
         let language = self.language();
-        parser.set_language(language)?;
         let files = self.files()?;
-        let query = self.query();
-        let mut ret: Vec<Element> = Vec::with_capacity(files.len());
+        let this = &*self;
 
         let start = Instant::now();
-        for file in files {
-            let file_handle = fs::File::open(&file.path)?;
-            let mut reader = BufReader::new(file_handle);
-            let mut source_code = String::new();
-            if let Err(err) = reader.read_to_string(&mut source_code) {
-                warn!("error while reading {}: {err}", file.path.clone());
-                continue;
-            };
-            let tree = match parser.parse(&source_code, None) {
-                Some(tree) => tree,
-                None => {
-                    warn!("error while parsing {}", file.path.clone());
-                    continue;
+        let ret = files
+            .par_iter()
+            .map_init(
+                || None,
+                |parser: &mut Option<TSParser>, file| {
+                    let parser = match parser {
+                        Some(parser) => parser,
+                        None => {
+                            let mut new = TSParser::new();
+                            new.set_language(language)?;
+                            parser.insert(new)
+                        },
+                    };
+                    parse_file(this, parser, file, pb)
                 },
-            };
-
-            let mut cursor = QueryCursor::new();
-
-            let matches = cursor.matches(query, tree.root_node(), source_code.as_bytes());
-            let res = collect_matches(matches, &source_code);
-            ret.append(
-                &mut res
-                    .into_iter()
-                    .map(|(line, index, name)| {
-                        pb.inc_length(1);
-                        Element {
-                            name: name.to_owned(),
-                            file: file.path.clone(),
-                            line,
-                            index,
-                        }
-                    })
-                    .collect::<Vec<Element>>(),
-            );
-        }
+            )
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<Element>>();
 
         debug!("Finding function took {:?}", start.elapsed());
 
@@ -51,6 +51,14 @@ impl super::Parser for LuaParser {
     fn query(&self) -> &Query {
         &self.query
     }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    fn definitions(&self) -> &[&str] {
+        &["function_declaration", "assignment_statement"]
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+// Written before clippy suggested `first` and `sort_by_key` here.
+#![allow(clippy::get_first, clippy::unnecessary_sort_by)]
 use std::error;
 
 use hotspots_discovery::{File, Lang};
@@ -67,6 +69,7 @@ fn returns_one_function_found() -> Result<(), DynError> {
     let want = Element {
         name: "func_one".to_owned(),
         line: 3,
+        end: 3,
         file: path,
         index: 0,
     };
@@ -91,24 +94,28 @@ fn can_identify_methods() -> Result<(), DynError> {
         Element {
             name: "method_one".to_owned(),
             line: 3,
+            end: 3,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "method_two".to_owned(),
             line: 5,
+            end: 5,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "method_three".to_owned(),
             line: 7,
+            end: 9,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "nested".to_owned(),
             line: 8,
+            end: 8,
             file: path,
             index: 0,
         },
@@ -137,18 +144,21 @@ fn returns_all_functions_in_files() -> Result<(), DynError> {
         Element {
             name: "func_five".to_owned(),
             line: 1,
+            end: 1,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "func_six".to_owned(),
             line: 2,
+            end: 2,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "method_one".to_owned(),
             line: 5,
+            end: 5,
             file: path,
             index: 0,
         },
@@ -183,24 +193,28 @@ fn handles_multiple_files() -> Result<(), DynError> {
         Element {
             name: "func_five".to_owned(),
             line: 1,
+            end: 1,
             file: path1.clone(),
             index: 0,
         },
         Element {
             name: "func_one".to_owned(),
             line: 3,
+            end: 3,
             file: path2,
             index: 0,
         },
         Element {
             name: "func_six".to_owned(),
             line: 2,
+            end: 2,
             file: path1.clone(),
             index: 0,
         },
         Element {
             name: "method_one".to_owned(),
             line: 5,
+            end: 5,
             file: path1,
             index: 0,
         },
@@ -0,0 +1,66 @@
+//! This module implements the parser for Python language.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+use tree_sitter_python::language;
+
+use super::Error;
+
+/// This parser can parse any Python files.
+pub struct PythonParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl PythonParser {
+    /// Creates a new Python parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/python.scm")
+            .ok_or(Error::FileNotFound("python.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(PythonParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for PythonParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Python
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their classes, like `Config.parse`.
+    fn scopes(&self) -> &[&str] {
+        &["class_definition"]
+    }
+}
+
+#[cfg(test)]
+mod tests;
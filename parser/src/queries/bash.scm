@@ -0,0 +1 @@
+(function_definition name: (word) @func_name)
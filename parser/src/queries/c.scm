@@ -0,0 +1,8 @@
+(function_definition
+  declarator: (function_declarator
+    declarator: (identifier) @func_name))
+
+(function_definition
+  declarator: (pointer_declarator
+    declarator: (function_declarator
+      declarator: (identifier) @func_name)))
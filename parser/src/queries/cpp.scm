@@ -0,0 +1,29 @@
+(function_definition
+  declarator: (function_declarator
+    declarator: [
+      (identifier)
+      (field_identifier)
+      (qualified_identifier)
+      (destructor_name)
+      (operator_name)
+    ] @func_name))
+
+(function_definition
+  declarator: (pointer_declarator
+    declarator: (function_declarator
+      declarator: [
+        (identifier)
+        (field_identifier)
+        (qualified_identifier)
+        (operator_name)
+      ] @func_name)))
+
+(function_definition
+  declarator: (reference_declarator
+    (function_declarator
+      declarator: [
+        (identifier)
+        (field_identifier)
+        (qualified_identifier)
+        (operator_name)
+      ] @func_name)))
@@ -0,0 +1,8 @@
+(method_declaration name: (identifier) @func_name)
+(constructor_declaration name: (identifier) @func_name)
+(compact_constructor_declaration name: (identifier) @func_name)
+
+(field_declaration
+  declarator: (variable_declarator
+    name: (identifier) @func_name
+    value: (lambda_expression)))
@@ -0,0 +1 @@
+(function_definition name: (identifier) @func_name)
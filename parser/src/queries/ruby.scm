@@ -0,0 +1,7 @@
+(method name: (_) @func_name)
+
+(singleton_method name: (_) @singleton_name)
+
+(singleton_class
+  body: (body_statement
+    (method name: (_) @singleton_name)))
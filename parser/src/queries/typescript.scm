@@ -0,0 +1,18 @@
+(function_declaration name: (identifier) @func_name)
+
+(generator_function_declaration name: (identifier) @func_name)
+
+(method_definition
+  name: [(property_identifier) (private_property_identifier)] @func_name)
+
+(variable_declarator
+  name: (identifier) @func_name
+  value: [(arrow_function) (function_expression) (generator_function)])
+
+(pair
+  key: (property_identifier) @func_name
+  value: [(arrow_function) (function_expression) (generator_function)])
+
+(public_field_definition
+  name: [(property_identifier) (private_property_identifier)] @func_name
+  value: [(arrow_function) (function_expression)])
@@ -0,0 +1,366 @@
+//! This module maps languages to their parsers, so files can be routed to the
+//! right parser without knowing which languages are supported.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+
+#[cfg(feature = "bash")]
+use crate::bash::BashParser;
+#[cfg(feature = "c")]
+use crate::c::CParser;
+#[cfg(feature = "cpp")]
+use crate::cpp::CppParser;
+use crate::custom::{CustomParser, Definition};
+#[cfg(feature = "go")]
+use crate::go::GoParser;
+#[cfg(feature = "java")]
+use crate::java::JavaParser;
+#[cfg(feature = "javascript")]
+use crate::javascript::JavaScriptParser;
+#[cfg(feature = "lua")]
+use crate::lua::LuaParser;
+#[cfg(feature = "python")]
+use crate::python::PythonParser;
+#[cfg(feature = "ruby")]
+use crate::ruby::RubyParser;
+#[cfg(feature = "rust")]
+use crate::rust::RustParser;
+#[cfg(feature = "typescript")]
+use crate::typescript::{TsxParser, TypeScriptParser};
+use crate::{Container, Error, Parser};
+
+/// Factory creates a parser with the given container.
+pub type Factory = Box<dyn Fn(Container) -> Result<Box<dyn Parser>, Error>>;
+
+/// Entry is a language with its factory, and its parser once a file of the
+/// language is added.
+struct Entry {
+    lang: Lang,
+    name: String,
+    query: Option<&'static str>,
+    factory: Factory,
+    parser: Option<Box<dyn Parser>>,
+}
+
+/// Registry routes files to the parsers of their languages. Parsers are
+/// created by `build`, or else when the first file of their language is added.
+pub struct Registry {
+    entries: Vec<Entry>,
+    filters: Vec<String>,
+    queries: Option<PathBuf>,
+}
+
+impl Default for Registry {
+    /// Returns a registry with all the built-in languages.
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut r = Registry::empty();
+        #[cfg(feature = "go")]
+        r.builtin(Lang::Go, "Go", "go", |c| Ok(Box::new(GoParser::new(c)?)));
+        #[cfg(feature = "rust")]
+        r.builtin(Lang::Rust, "Rust", "rust", |c| Ok(Box::new(RustParser::new(c)?)));
+        #[cfg(feature = "lua")]
+        r.builtin(Lang::Lua, "Lua", "lua", |c| Ok(Box::new(LuaParser::new(c)?)));
+        #[cfg(feature = "python")]
+        r.builtin(Lang::Python, "Python", "python", |c| Ok(Box::new(PythonParser::new(c)?)));
+        #[cfg(feature = "javascript")]
+        r.builtin(Lang::JavaScript, "JavaScript", "javascript", |c| {
+            Ok(Box::new(JavaScriptParser::new(c)?))
+        });
+        #[cfg(feature = "typescript")]
+        r.builtin(Lang::TypeScript, "TypeScript", "typescript", |c| {
+            Ok(Box::new(TypeScriptParser::new(c)?))
+        });
+        #[cfg(feature = "typescript")]
+        r.builtin(Lang::Tsx, "TSX", "typescript", |c| Ok(Box::new(TsxParser::new(c)?)));
+        #[cfg(feature = "c")]
+        r.builtin(Lang::C, "C", "c", |c| Ok(Box::new(CParser::new(c)?)));
+        #[cfg(feature = "cpp")]
+        r.builtin(Lang::Cpp, "C++", "cpp", |c| Ok(Box::new(CppParser::new(c)?)));
+        #[cfg(feature = "java")]
+        r.builtin(Lang::Java, "Java", "java", |c| Ok(Box::new(JavaParser::new(c)?)));
+        #[cfg(feature = "ruby")]
+        r.builtin(Lang::Ruby, "Ruby", "ruby", |c| Ok(Box::new(RubyParser::new(c)?)));
+        #[cfg(feature = "bash")]
+        r.builtin(Lang::Bash, "Bash", "bash", |c| Ok(Box::new(BashParser::new(c)?)));
+        r
+    }
+}
+
+impl Registry {
+    /// Returns a registry without any languages.
+    pub fn empty() -> Self {
+        Registry {
+            entries: Vec::new(),
+            filters: Vec::new(),
+            queries: None,
+        }
+    }
+
+    /// Registers the factory of the parser for the language, replacing any
+    /// previous one.
+    pub fn register<F>(&mut self, lang: Lang, name: &str, factory: F)
+    where
+        F: Fn(Container) -> Result<Box<dyn Parser>, Error> + 'static,
+    {
+        self.entries.retain(|e| e.lang != lang);
+        self.entries.push(Entry {
+            lang,
+            name: name.to_owned(),
+            query: None,
+            factory: Box::new(factory),
+            parser: None,
+        });
+    }
+
+    /// Registers a built-in language, whose embedded query can be overridden
+    /// by the file with the query name.
+    // Unused when all the languages are disabled.
+    #[allow(dead_code)]
+    fn builtin<F>(&mut self, lang: Lang, name: &str, query: &'static str, factory: F)
+    where
+        F: Fn(Container) -> Result<Box<dyn Parser>, Error> + 'static,
+    {
+        self.register(lang, name, factory);
+        if let Some(entry) = self.entries.last_mut() {
+            entry.query = Some(query);
+        }
+    }
+
+    /// Overrides the embedded queries with the `<lang>.scm` files in the
+    /// directory, when they exist. A file with a `; extends` line is added to
+    /// the embedded query, otherwise it replaces it.
+    pub fn override_queries<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.queries = Some(dir.into());
+    }
+
+    /// Registers a language defined at runtime.
+    pub fn register_custom(&mut self, def: Definition) {
+        let lang = Lang::Custom(def.name.clone());
+        let name = def.name.clone();
+        self.register(lang, &name, move |c| Ok(Box::new(CustomParser::new(c, &def)?)));
+    }
+
+    /// Returns true if the language has a parser.
+    pub fn supports(&self, lang: &Lang) -> bool {
+        self.entries.iter().any(|e| e.lang == *lang)
+    }
+
+    /// Returns the names of the supported languages.
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Adds the filter for excluding functions to all parsers.
+    pub fn filter_name(&mut self, s: String) {
+        for parser in self.entries.iter_mut().filter_map(|e| e.parser.as_mut()) {
+            parser.filter_name(s.clone());
+        }
+        self.filters.push(s);
+    }
+
+    /// Creates the parsers of all the languages, with the queries of the
+    /// project, so a broken grammar or query is reported before any file is
+    /// added. It returns the first error.
+    pub fn build(&mut self) -> Result<(), Error> {
+        for entry in self.entries.iter_mut().filter(|e| e.parser.is_none()) {
+            entry.parser = Some(create(entry, &self.queries, &self.filters)?);
+        }
+        Ok(())
+    }
+
+    /// Adds the file to the parser of its language, creating the parser if
+    /// needed. It returns an error if the language is not supported or the
+    /// parser can't be created.
+    pub fn add_file(&mut self, f: File) -> Result<(), Error> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.lang == f.lang)
+            .ok_or(Error::NotCompatible)?;
+        let parser = match &mut entry.parser {
+            Some(parser) => parser,
+            None => {
+                let parser = create(entry, &self.queries, &self.filters)?;
+                entry.parser.insert(parser)
+            },
+        };
+        parser.add_file(f)
+    }
+
+    /// Returns the parsers that have files, with the names of their languages,
+    /// in the order the languages were registered.
+    pub fn into_parsers(self) -> Vec<(String, Box<dyn Parser>)> {
+        self.entries
+            .into_iter()
+            .filter_map(|e| Some((e.name, e.parser?)))
+            .filter(|(_, parser)| parser.files().is_ok())
+            .collect()
+    }
+}
+
+/// Returns the parser of the entry, with the query of the project and the
+/// filters.
+fn create(
+    entry: &Entry,
+    queries: &Option<PathBuf>,
+    filters: &[String],
+) -> Result<Box<dyn Parser>, Error> {
+    let mut parser = (entry.factory)(Container::new(100))?;
+    if let (Some(dir), Some(name)) = (queries, entry.query) {
+        let path = dir.join(format!("{name}.scm"));
+        if path.is_file() {
+            parser.set_query(project_query(parser.language(), name, &path)?);
+        }
+    }
+    for s in filters {
+        parser.filter_name(s.clone());
+    }
+    Ok(parser)
+}
+
+/// Returns the query in the file, added to the embedded query of the language
+/// if the file extends it.
+fn project_query(language: Language, name: &str, path: &Path) -> Result<Query, Error> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| Error::FileNotFound(format!("{}: {err}", path.display())))?;
+    let extends = source.lines().any(|l| {
+        l.strip_prefix(';')
+            .map_or(false, |c| c.trim_start_matches(';').trim() == "extends")
+    });
+    if !extends {
+        return crate::compile_query(language, "", &source, path);
+    }
+    let embedded = crate::PROJECT_DIR
+        .get_file(format!("src/queries/{name}.scm"))
+        .and_then(|f| f.contents_utf8())
+        .ok_or_else(|| Error::FileNotFound(format!("{name}.scm not found")))?;
+    crate::compile_query(language, &format!("{embedded}\n"), &source, path)
+}
+
+#[cfg(all(test, feature = "go", feature = "rust"))]
+mod tests {
+    use indicatif::ProgressBar as pb;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn file(path: &str, lang: Lang) -> File {
+        File {
+            path: path.to_owned(),
+            lang,
+        }
+    }
+
+    #[test]
+    fn routes_files() {
+        let mut r = Registry::default();
+        let res = r.add_file(file("src/fixtures/rust/one_function.rs", Lang::Rust));
+        assert_that!(res).is_ok();
+        let res = r.add_file(file("src/fixtures/go/one_function.1.go", Lang::Go));
+        assert_that!(res).is_ok();
+        let res = r.add_file(file("README.md", Lang::Undefined));
+        assert_that!(matches!(res, Err(Error::NotCompatible))).is_true();
+
+        let parsers = r.into_parsers();
+        let names: Vec<&str> = parsers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_that!(names).is_equal_to(vec!["Go", "Rust"]);
+    }
+
+    #[test]
+    fn filters_functions() {
+        let mut r = Registry::default();
+        r.filter_name("func_two".to_owned());
+        let path = "src/fixtures/rust/multi_functions.rs";
+        r.add_file(file(path, Lang::Rust)).unwrap();
+        r.filter_name("func_three".to_owned());
+
+        let (_, mut parser) = r.into_parsers().pop().unwrap();
+        let names: Vec<String> = parser
+            .find_functions(&pb::hidden())
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_that!(names).does_not_contain("func_two".to_owned());
+        assert_that!(names).does_not_contain("func_three".to_owned());
+        assert_that!(names).is_not_empty();
+    }
+
+    fn rust_functions(queries: &str, path: &str) -> Result<Vec<String>, Error> {
+        let mut r = Registry::default();
+        r.override_queries(format!("src/fixtures/queries/{queries}"));
+        r.add_file(file(path, Lang::Rust))?;
+        let (_, mut parser) = r.into_parsers().pop().unwrap();
+        let mut names: Vec<String> = parser
+            .find_functions(&pb::hidden())?
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    #[test]
+    fn overrides_queries() {
+        let names = rust_functions("replace", "src/fixtures/rust/multi_functions.rs").unwrap();
+        assert_that!(names).is_equal_to(vec!["func_three".to_owned(), "func_two".to_owned()]);
+
+        let names = rust_functions("extend", "src/fixtures/rust/method.rs").unwrap();
+        assert_that!(names).contains("x".to_owned());
+        assert_that!(names).contains("func_one".to_owned());
+
+        let names = rust_functions("missing", "src/fixtures/rust/multi_functions.rs").unwrap();
+        assert_that!(names).contains("nested".to_owned());
+    }
+
+    #[test]
+    fn reports_query_errors() {
+        let res = rust_functions("invalid", "src/fixtures/rust/multi_functions.rs");
+        let err = res.unwrap_err().to_string();
+        assert_that!(err.as_str()).contains("src/fixtures/queries/invalid/rust.scm:3:");
+        assert_that!(err.as_str()).contains("Field error: nmae");
+
+        let res = rust_functions("invalid-extend", "src/fixtures/rust/multi_functions.rs");
+        let err = res.unwrap_err().to_string();
+        assert_that!(err.as_str()).contains("src/fixtures/queries/invalid-extend/rust.scm:3:");
+        assert_that!(err.as_str()).contains("Field error: nmae");
+    }
+
+    #[test]
+    fn builds_parsers_up_front() {
+        let mut r = Registry::default();
+        r.override_queries("src/fixtures/queries/invalid");
+        let err = r.build().unwrap_err().to_string();
+        assert_that!(err.as_str()).contains("src/fixtures/queries/invalid/rust.scm:3:");
+
+        let mut r = Registry::default();
+        r.register_custom(Definition {
+            name: "dsl".to_owned(),
+            extensions: vec!["dsl".to_owned()],
+            grammar: "missing.so".into(),
+            query: "missing.scm".into(),
+            symbol: None,
+        });
+        assert_that!(matches!(r.build(), Err(Error::Grammar(_)))).is_true();
+
+        let mut r = Registry::default();
+        assert_that!(r.build()).is_ok();
+        r.add_file(file("src/fixtures/rust/one_function.rs", Lang::Rust)).unwrap();
+        let parsers = r.into_parsers();
+        let names: Vec<&str> = parsers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_that!(names).is_equal_to(vec!["Rust"]);
+    }
+
+    #[test]
+    fn replaces_languages() {
+        let mut r = Registry::empty();
+        r.register(Lang::Go, "Go", |c| Ok(Box::new(GoParser::new(c)?)));
+        r.register(Lang::Go, "Golang", |c| Ok(Box::new(GoParser::new(c)?)));
+        assert_that!(r.supports(&Lang::Go)).is_true();
+        assert_that!(r.supports(&Lang::Rust)).is_false();
+        assert_that!(r.languages().collect::<Vec<_>>()).is_equal_to(vec!["Golang"]);
+    }
+}
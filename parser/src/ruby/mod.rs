@@ -0,0 +1,101 @@
+//! This module implements the parser for Ruby language.
+use std::collections::HashSet;
+
+use hotspots_discovery::{File, Lang};
+use hotspots_grammars::ruby as language;
+use tree_sitter::{Language, Query};
+
+use super::{Element, Error};
+
+/// This parser can parse any Ruby files.
+pub struct RubyParser {
+    container: super::Container,
+    query: Query,
+}
+
+impl RubyParser {
+    /// Creates a new Ruby parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        let queries = crate::PROJECT_DIR
+            .get_file("src/queries/ruby.scm")
+            .ok_or(Error::FileNotFound("ruby.scm not found".to_owned()))?;
+        let query = queries
+            .contents_utf8()
+            .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+        let language = language();
+        let query = Query::new(language, query)?;
+
+        Ok(RubyParser {
+            query,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for RubyParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Ruby
+    }
+
+    fn language(&self) -> Language {
+        language()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their modules and classes.
+    fn scopes(&self) -> &[&str] {
+        &["module", "class"]
+    }
+
+    fn separator(&self) -> &str {
+        "::"
+    }
+
+    /// Returns the names in the Ruby notation, where instance methods follow
+    /// their module or class after a `#` and singleton methods after a `.`,
+    /// like `Ops::Deploy#run` and `Ops::Deploy.build`. Methods inside
+    /// `class << self` are matched as both, and only kept as singleton ones.
+    fn func_repr(&self, v: Vec<Element>) -> (Vec<Element>, usize) {
+        let singleton = self.query.capture_index_for_name("singleton_name");
+        let singletons: HashSet<(String, usize)> = v
+            .iter()
+            .filter(|e| Some(e.index) == singleton)
+            .map(|e| (e.file.clone(), e.line))
+            .collect();
+        let len = v.len();
+        let res: Vec<Element> = v
+            .into_iter()
+            .filter(|e| {
+                Some(e.index) == singleton || !singletons.contains(&(e.file.clone(), e.line))
+            })
+            .map(|mut e| {
+                if let Some((scope, name)) = e.name.rsplit_once("::") {
+                    let separator = if Some(e.index) == singleton { "." } else { "#" };
+                    e.name = format!("{scope}{separator}{name}");
+                }
+                e
+            })
+            .collect();
+        let redacted = len - res.len();
+        (res, redacted)
+    }
+}
+
+#[cfg(test)]
+mod tests;
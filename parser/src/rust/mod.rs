@@ -51,6 +51,10 @@ impl super::Parser for RustParser {
     fn query(&self) -> &Query {
         &self.query
     }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+// Written before clippy suggested `first` and `sort_by_key` here.
+#![allow(clippy::get_first, clippy::unnecessary_sort_by)]
 use std::error;
 
 use hotspots_discovery::{File, Lang};
@@ -67,6 +69,7 @@ fn returns_one_function_found() -> Result<(), DynError> {
     let want = Element {
         name: "func_one".to_owned(),
         line: 1,
+        end: 1,
         file: path,
         index: 0,
     };
@@ -91,24 +94,28 @@ fn can_identify_methods() -> Result<(), DynError> {
         Element {
             name: "func_one".to_owned(),
             line: 4,
+            end: 4,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "func_two".to_owned(),
             line: 5,
+            end: 7,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "nested".to_owned(),
             line: 6,
+            end: 6,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "func_three".to_owned(),
             line: 8,
+            end: 8,
             file: path,
             index: 0,
         },
@@ -137,18 +144,21 @@ fn returns_all_functions_in_files() -> Result<(), DynError> {
         Element {
             name: "func_two".to_owned(),
             line: 1,
+            end: 1,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "func_three".to_owned(),
             line: 3,
+            end: 5,
             file: path.clone(),
             index: 0,
         },
         Element {
             name: "nested".to_owned(),
             line: 4,
+            end: 4,
             file: path,
             index: 0,
         },
@@ -183,24 +193,28 @@ fn handles_multiple_files() -> Result<(), DynError> {
         Element {
             name: "func_one".to_owned(),
             line: 1,
+            end: 1,
             file: path2,
             index: 0,
         },
         Element {
             name: "func_two".to_owned(),
             line: 1,
+            end: 1,
             file: path1.clone(),
             index: 0,
         },
         Element {
             name: "func_three".to_owned(),
             line: 3,
+            end: 5,
             file: path1.clone(),
             index: 0,
         },
         Element {
             name: "nested".to_owned(),
             line: 4,
+            end: 4,
             file: path1,
             index: 0,
         },
@@ -211,3 +225,30 @@ fn handles_multiple_files() -> Result<(), DynError> {
     assert_equal(want, res);
     Ok(())
 }
+
+#[test]
+fn keeps_file_order() -> Result<(), DynError> {
+    let paths = [
+        format!("{FIXTURES}/multi_functions.rs"),
+        format!("{FIXTURES}/one_function.rs"),
+        format!("{FIXTURES}/method.rs"),
+        format!("{FIXTURES}/multi_functions.rs"),
+    ];
+    for _ in 0..5 {
+        let mut p = RustParser::new(Container::new(100))?;
+        for path in &paths {
+            p.add_file(File {
+                path: path.clone(),
+                lang: Lang::Rust,
+            })?;
+        }
+        let mut files: Vec<String> = p
+            .find_functions(&pb::hidden())?
+            .into_iter()
+            .map(|e| e.file)
+            .collect();
+        files.dedup();
+        assert_that!(files).is_equal_to(paths.to_vec());
+    }
+    Ok(())
+}
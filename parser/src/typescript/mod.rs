@@ -0,0 +1,125 @@
+//! This module implements the parsers for TypeScript language, with and
+//! without JSX.
+use hotspots_discovery::{File, Lang};
+use tree_sitter::{Language, Query};
+use tree_sitter_typescript::{language_tsx, language_typescript};
+
+use super::Error;
+
+/// Kinds of nodes that qualify the functions inside them.
+const SCOPES: &[&str] = &[
+    "class_declaration",
+    "abstract_class_declaration",
+    "class",
+    "object",
+];
+
+/// This parser can parse any TypeScript files.
+pub struct TypeScriptParser {
+    container: super::Container,
+    query: Query,
+}
+
+/// This parser can parse any TSX files.
+pub struct TsxParser {
+    container: super::Container,
+    query: Query,
+}
+
+/// Returns the query for the language.
+fn query(language: Language) -> Result<Query, Error> {
+    let queries = crate::PROJECT_DIR
+        .get_file("src/queries/typescript.scm")
+        .ok_or(Error::FileNotFound("typescript.scm not found".to_owned()))?;
+    let query = queries
+        .contents_utf8()
+        .ok_or(Error::ParseFile("Can't parse queries".to_owned()))?;
+    Ok(Query::new(language, query)?)
+}
+
+impl TypeScriptParser {
+    /// Creates a new TypeScript parser. The container should have enough
+    /// capacity or capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        Ok(TypeScriptParser {
+            query: query(language_typescript())?,
+            container: c,
+        })
+    }
+}
+
+impl TsxParser {
+    /// Creates a new TSX parser. The container should have enough capacity or
+    /// capable of growing to hold all the elements in the file.
+    pub fn new(c: super::Container) -> Result<Self, Error> {
+        Ok(TsxParser {
+            query: query(language_tsx())?,
+            container: c,
+        })
+    }
+}
+
+impl super::Parser for TypeScriptParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::TypeScript
+    }
+
+    fn language(&self) -> Language {
+        language_typescript()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their classes or objects, like `Config.parse`.
+    fn scopes(&self) -> &[&str] {
+        SCOPES
+    }
+}
+
+impl super::Parser for TsxParser {
+    fn container(&mut self) -> &mut super::Container {
+        &mut self.container
+    }
+
+    fn ro_container(&self) -> &super::Container {
+        &self.container
+    }
+
+    fn supported(&self, f: &File) -> bool {
+        f.lang == Lang::Tsx
+    }
+
+    fn language(&self) -> Language {
+        language_tsx()
+    }
+
+    fn query(&self) -> &Query {
+        &self.query
+    }
+
+    fn set_query(&mut self, query: Query) {
+        self.query = query;
+    }
+
+    /// Methods are qualified by their classes or objects, like `Config.parse`.
+    fn scopes(&self) -> &[&str] {
+        SCOPES
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -3,6 +3,7 @@
 use std::fs::File;
 use std::io;
 use std::path::Path;
+use std::process::Command;
 
 use git2::{Repository, RepositoryInitOptions};
 use tempfile::TempDir;
@@ -40,3 +41,38 @@ pub fn repo_init() -> (TempDir, Repository) {
     }
     (td, repo)
 }
+
+/// Creates a repository with the git binary in the given object format, as
+/// libgit2 can only create SHA-1 repositories.
+pub fn cli_repo_init(object_format: &str) -> io::Result<TempDir> {
+    let td = TempDir::new()?;
+    let format = format!("--object-format={object_format}");
+    git(&td, &["init", "-q", "-b", "master", &format])?;
+    git(&td, &["config", "user.name", "name"])?;
+    git(&td, &["config", "user.email", "email"])?;
+    Ok(td)
+}
+
+/// Writes the contents into the file in the repository and commits it with
+/// the given message.
+pub fn commit_file(td: &TempDir, file: &str, contents: &str, message: &str) -> io::Result<()> {
+    let path = td.path().join(file);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, contents)?;
+    git(td, &["add", file])?;
+    git(td, &["commit", "-q", "-m", message])
+}
+
+/// Runs git with the arguments in the repository. It returns an error if the
+/// command fails.
+pub fn git(td: &TempDir, args: &[&str]) -> io::Result<()> {
+    let output = Command::new("git").args(args).current_dir(td.path()).output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ))
+    }
+}